@@ -0,0 +1,22 @@
+#!/bin/bash
+
+# Checks that the integer-only statistics of `HistoryBuffer` (see `nofpu/src/lib.rs`) don't call
+# the soft-float routines on a target without an FPU
+
+set -euo pipefail
+
+main() {
+    local target=thumbv6m-none-eabi
+
+    cd nofpu
+    cargo rustc --release --target $target -- --emit=obj=target/nofpu.o
+
+    # the ARM EABI soft-float routines are `__aeabi_f*` / `__aeabi_d*` and the conversions
+    # `__aeabi_*2f` / `__aeabi_*2d`
+    if nm -u target/nofpu.o | grep -E '__aeabi_([df]|\w+2[df]$)'; then
+        echo "error: floating point routines found in the integer-only statistics"
+        exit 1
+    fi
+}
+
+main
@@ -0,0 +1,31 @@
+//! Instantiates the integer-only statistics of `HistoryBuffer` so that `no-fpu.sh` can check that
+//! they don't call the soft-float routines on a target without an FPU
+
+#![no_std]
+
+use heapless::HistoryBuffer;
+
+#[no_mangle]
+pub fn mean_u8(x: &HistoryBuffer<u8, 16>) -> Option<u32> {
+    x.mean_u32()
+}
+
+#[no_mangle]
+pub fn mean_u16(x: &HistoryBuffer<u16, 16>) -> Option<u32> {
+    x.mean_u32()
+}
+
+#[no_mangle]
+pub fn mean_u32(x: &HistoryBuffer<u32, 16>) -> Option<u32> {
+    x.mean_u32()
+}
+
+#[no_mangle]
+pub fn summary_mean_i64(x: &HistoryBuffer<i64, 16>) -> Option<i64> {
+    x.numeric_summary().mean
+}
+
+#[no_mangle]
+pub fn summary_mean_u32(x: &HistoryBuffer<u32, 16>) -> Option<u32> {
+    x.numeric_summary().mean
+}
@@ -0,0 +1,147 @@
+use crate::{sealed::vec::LenType, string::floor_char_boundary, IndexMap, String, Vec};
+use ::arbitrary::{size_hint, Arbitrary, Result, Unstructured};
+use hash32::{BuildHasher, Hash};
+
+/// Generates at most `N` elements; generation stops once the vector is full.
+impl<'a, T, L: LenType, const N: usize> Arbitrary<'a> for Vec<T, N, L>
+where
+    T: Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut vec = Vec::new();
+        let mut iter = u.arbitrary_iter()?;
+        // NOTE check for room first, so that no input is consumed by an element that doesn't fit
+        while !vec.is_full() {
+            match iter.next() {
+                Some(elem) => {
+                    // NOTE(unsafe) the vector is not full
+                    unsafe { vec.push_unchecked(elem?) }
+                }
+                None => break,
+            }
+        }
+        Ok(vec)
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        let mut vec = Vec::new();
+        for elem in u.arbitrary_take_rest_iter()? {
+            if vec.push(elem?).is_err() {
+                break;
+            }
+        }
+        Ok(vec)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+/// Generates a string of at most `N` bytes, truncating it at a character boundary.
+impl<'a, L: LenType, const N: usize> Arbitrary<'a> for String<N, L> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        <&str as Arbitrary>::arbitrary(u).map(truncate)
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        <&str as Arbitrary>::arbitrary_take_rest(u).map(truncate)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <&str as Arbitrary>::size_hint(depth)
+    }
+}
+
+/// Converts the longest prefix of `s` that fits in `N` bytes
+fn truncate<L: LenType, const N: usize>(s: &str) -> String<N, L> {
+    let end = floor_char_boundary(s, N);
+
+    let mut string = String::new();
+    // NOTE(unwrap) `end <= N`
+    string.push_str(&s[..end]).unwrap();
+    string
+}
+
+/// Generates at most `N` entries; generation stops once the map is full.
+impl<'a, K, V, S, const N: usize> Arbitrary<'a> for IndexMap<K, V, S, N>
+where
+    K: Arbitrary<'a> + Eq + Hash,
+    V: Arbitrary<'a>,
+    S: BuildHasher + Default,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut map = IndexMap::default();
+        let mut iter = u.arbitrary_iter::<(K, V)>()?;
+        while map.len() < map.capacity() {
+            match iter.next() {
+                Some(entry) => {
+                    let (k, v) = entry?;
+                    // NOTE(unwrap) the map is not full
+                    map.insert(k, v).ok().unwrap();
+                }
+                None => break,
+            }
+        }
+        Ok(map)
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        let mut map = IndexMap::default();
+        for entry in u.arbitrary_take_rest_iter::<(K, V)>()? {
+            let (k, v) = entry?;
+            if map.insert(k, v).is_err() {
+                break;
+            }
+        }
+        Ok(map)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and(<usize as Arbitrary>::size_hint(depth), (0, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FnvIndexMap;
+
+    #[test]
+    fn vec() {
+        let data = [0xff; 64];
+
+        let v: Vec<u8, 4> = Unstructured::new(&data).arbitrary().unwrap();
+        assert_eq!(v, [0xff; 4]);
+
+        let v = Vec::<u16, 3>::arbitrary_take_rest(Unstructured::new(&data)).unwrap();
+        assert_eq!(v.len(), 3);
+
+        let v: Vec<u8, 4> = Unstructured::new(&[]).arbitrary().unwrap();
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn string() {
+        // "h" followed by three 2-byte characters
+        let data = "h\u{e9}\u{e9}\u{e9}".as_bytes();
+
+        let s = String::<4>::arbitrary_take_rest(Unstructured::new(data)).unwrap();
+        assert_eq!(s, "h\u{e9}");
+
+        let s = String::<16>::arbitrary_take_rest(Unstructured::new(data)).unwrap();
+        assert_eq!(s, "h\u{e9}\u{e9}\u{e9}");
+    }
+
+    #[test]
+    fn index_map() {
+        // "continue" flag, key, value
+        let data: Vec<u8, 24> = (0..8).flat_map(|i| [1, 2 * i + 1, 1]).collect();
+
+        let map: FnvIndexMap<u8, u8, 4> = Unstructured::new(&data).arbitrary().unwrap();
+        assert!(map.len() <= 4);
+
+        let map = FnvIndexMap::<u8, u8, 2>::arbitrary_take_rest(Unstructured::new(&data)).unwrap();
+        assert_eq!(map.len(), 2);
+    }
+}
@@ -163,6 +163,39 @@ where
         self.len() == 0
     }
 
+    /// Checks if the binary heap is full.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// let mut heap: BinaryHeap<_, Max, 2> = BinaryHeap::new();
+    /// heap.push(3).unwrap();
+    ///
+    /// assert!(!heap.is_full());
+    ///
+    /// heap.push(5).unwrap();
+    ///
+    /// assert!(heap.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        self.data.is_full()
+    }
+
+    /// Returns the number of elements that can still be pushed into the binary heap.
+    ///
+    /// ```
+    /// use heapless::binary_heap::{BinaryHeap, Max};
+    ///
+    /// let mut heap: BinaryHeap<_, Max, 8> = BinaryHeap::new();
+    /// heap.push(1).unwrap();
+    /// heap.push(3).unwrap();
+    ///
+    /// assert_eq!(heap.remaining_capacity(), 6);
+    /// ```
+    pub fn remaining_capacity(&self) -> usize {
+        self.data.remaining_capacity()
+    }
+
     /// Returns an iterator visiting all values in the underlying vector, in arbitrary order.
     ///
     /// ```
@@ -0,0 +1,265 @@
+//! A fixed capacity, nul-terminated C string.
+//!
+//! See [`CString`] for details. This module is only available with the `c-string` feature, which
+//! requires Rust 1.64 or newer.
+
+use core::{
+    ffi::{c_char, CStr},
+    fmt,
+    mem::MaybeUninit,
+    ops,
+};
+
+use crate::{vec::CapacityError, Vec};
+
+struct Assert<const N: usize>;
+
+impl<const N: usize> Assert<N> {
+    const NOT_EMPTY: () = assert!(N > 0, "there is no room for the nul terminator");
+}
+
+/// A fixed capacity, owned, C-compatible string: nul-terminated and without nul bytes in the
+/// middle
+///
+/// The nul terminator is stored in the buffer, so a `CString<N>` holds up to `N - 1` bytes.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::CString;
+///
+/// let mut name: CString<16> = CString::from_bytes(b"heapless").unwrap();
+/// name.extend_from_bytes(b"-ap").unwrap();
+///
+/// assert_eq!(name.as_bytes_with_nul(), b"heapless-ap\0");
+///
+/// // e.g. `extern "C" { fn set_hostname(name: *const c_char); }`
+/// let ptr = name.as_ptr();
+/// # let _ = ptr;
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct CString<const N: usize> {
+    // always ends with the nul terminator
+    inner: Vec<u8, N>,
+}
+
+impl<const N: usize> CString<N> {
+    /// Creates an empty C string, which only holds the nul terminator
+    ///
+    /// A `CString<0>` has no room for the nul terminator and is rejected at compile time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::CString;
+    ///
+    /// static EMPTY: CString<8> = CString::new();
+    /// assert_eq!(EMPTY.as_bytes_with_nul(), b"\0");
+    /// ```
+    ///
+    /// ``` compile_fail
+    /// use heapless::CString;
+    ///
+    /// let s: CString<0> = CString::new();
+    /// ```
+    pub const fn new() -> Self {
+        let () = Assert::<N>::NOT_EMPTY;
+
+        // NOTE(unsafe) `N > 0`, so the nul terminator fits; the whole buffer is zeroed because
+        // it can't be written in part in a `const fn`
+        let inner = unsafe { Vec::from_raw_parts(MaybeUninit::new([0; N]), 1) };
+        Self { inner }
+    }
+
+    /// Creates a C string from bytes that don't include the nul terminator
+    ///
+    /// Returns an error if `bytes` contains a nul byte, or if it doesn't fit along with the nul
+    /// terminator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::c_string::{CString, ExtendError};
+    ///
+    /// let s: CString<4> = CString::from_bytes(b"abc").unwrap();
+    /// assert_eq!(s.as_bytes(), b"abc");
+    ///
+    /// assert!(matches!(
+    ///     CString::<4>::from_bytes(b"abcd"),
+    ///     Err(ExtendError::Capacity(_))
+    /// ));
+    /// assert!(matches!(
+    ///     CString::<4>::from_bytes(b"a\0c"),
+    ///     Err(ExtendError::InteriorNul { position: 1 })
+    /// ));
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ExtendError> {
+        let mut s = Self::new();
+        s.extend_from_bytes(bytes)?;
+        Ok(s)
+    }
+
+    /// Appends bytes, which must not include a nul byte, before the nul terminator
+    ///
+    /// Returns an error, leaving the C string untouched, if `bytes` contains a nul byte or if it
+    /// doesn't fit.
+    pub fn extend_from_bytes(&mut self, bytes: &[u8]) -> Result<(), ExtendError> {
+        if let Some(position) = bytes.iter().position(|&byte| byte == 0) {
+            return Err(ExtendError::InteriorNul { position });
+        }
+
+        if bytes.len() > self.inner.remaining_capacity() {
+            return Err(ExtendError::Capacity(CapacityError));
+        }
+
+        // move the nul terminator to the new end
+        let len = self.inner.len();
+        // NOTE(unwrap) there's room for `bytes`
+        self.inner.extend_from_slice(bytes).unwrap();
+        self.inner[len - 1..].rotate_left(1);
+
+        Ok(())
+    }
+
+    /// Appends a byte, which must not be nul, before the nul terminator
+    ///
+    /// Returns an error, leaving the C string untouched, if `byte` is nul or if it doesn't fit.
+    pub fn push(&mut self, byte: u8) -> Result<(), ExtendError> {
+        self.extend_from_bytes(&[byte])
+    }
+
+    /// Removes all the bytes except for the nul terminator
+    pub fn clear(&mut self) {
+        self.inner.truncate(1);
+        self.inner[0] = 0;
+    }
+
+    /// Returns the maximum number of bytes the C string can hold, not counting the nul
+    /// terminator
+    pub fn capacity(&self) -> usize {
+        N - 1
+    }
+
+    /// Returns the contents of the C string, without the nul terminator
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.inner[..self.inner.len() - 1]
+    }
+
+    /// Returns the contents of the C string, including the nul terminator
+    pub fn as_bytes_with_nul(&self) -> &[u8] {
+        &self.inner
+    }
+
+    /// Returns the contents as a [`CStr`]
+    pub fn as_c_str(&self) -> &CStr {
+        // NOTE(unsafe) `inner` ends with the only nul byte it contains
+        unsafe { CStr::from_bytes_with_nul_unchecked(&self.inner) }
+    }
+
+    /// Returns a pointer to the nul-terminated contents, to pass to C functions
+    ///
+    /// The pointer is valid as long as the C string isn't modified, moved or dropped.
+    pub fn as_ptr(&self) -> *const c_char {
+        self.inner.as_ptr() as *const c_char
+    }
+}
+
+impl<const N: usize> Default for CString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> ops::Deref for CString<N> {
+    type Target = CStr;
+
+    fn deref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl<const N: usize> AsRef<CStr> for CString<N> {
+    #[inline]
+    fn as_ref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl<const N: usize> fmt::Debug for CString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <CStr as fmt::Debug>::fmt(self, f)
+    }
+}
+
+/// The error returned when bytes can't be added to a [`CString`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtendError {
+    /// The bytes don't fit
+    Capacity(CapacityError),
+    /// The bytes contain a nul byte at `position`
+    InteriorNul {
+        /// The position of the first nul byte
+        position: usize,
+    },
+}
+
+impl fmt::Display for ExtendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtendError::Capacity(e) => e.fmt(f),
+            ExtendError::InteriorNul { position } => {
+                write!(f, "nul byte found at position {}", position)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CString, ExtendError};
+    use crate::vec::CapacityError;
+
+    #[test]
+    fn empty() {
+        let s: CString<1> = CString::new();
+        assert_eq!(s.as_bytes(), b"");
+        assert_eq!(s.as_bytes_with_nul(), b"\0");
+        assert_eq!(s.capacity(), 0);
+        assert_eq!(s.as_c_str().to_bytes(), b"");
+    }
+
+    #[test]
+    fn extend() {
+        let mut s: CString<6> = CString::new();
+        s.push(b'a').unwrap();
+        s.extend_from_bytes(b"bc").unwrap();
+        s.extend_from_bytes(b"").unwrap();
+
+        assert_eq!(
+            s.extend_from_bytes(b"def"),
+            Err(ExtendError::Capacity(CapacityError))
+        );
+        assert_eq!(
+            s.extend_from_bytes(b"d\0"),
+            Err(ExtendError::InteriorNul { position: 1 })
+        );
+        assert_eq!(s.push(0), Err(ExtendError::InteriorNul { position: 0 }));
+        assert_eq!(s.as_bytes_with_nul(), b"abc\0");
+
+        s.extend_from_bytes(b"de").unwrap();
+        assert_eq!(s.as_bytes_with_nul(), b"abcde\0");
+        assert_eq!(s.to_str(), Ok("abcde"));
+
+        s.clear();
+        assert_eq!(s.as_bytes_with_nul(), b"\0");
+    }
+
+    #[test]
+    fn as_ptr() {
+        let s: CString<8> = CString::from_bytes(b"abc").unwrap();
+        let ptr = s.as_ptr();
+        let c_str = unsafe { core::ffi::CStr::from_ptr(ptr) };
+        assert_eq!(c_str, s.as_c_str());
+        assert_eq!(std::format!("{:?}", s), "\"abc\"");
+    }
+}
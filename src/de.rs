@@ -1,6 +1,12 @@
+//! Serde helpers
+//!
+//! The containers implement `Deserialize` when the `serde` feature is enabled; this module holds
+//! the alternative deserialization modes.
+
 use crate::{
-    sealed::binary_heap::Kind as BinaryHeapKind, BinaryHeap, IndexMap, IndexSet, LinearMap, String,
-    Vec,
+    sealed::{binary_heap::Kind as BinaryHeapKind, de::Truncate, vec::LenType},
+    string::floor_char_boundary,
+    BinaryHeap, HistoryBuffer, IndexMap, IndexSet, LinearMap, String, Vec,
 };
 use core::{fmt, marker::PhantomData};
 use hash32::{BuildHasherDefault, Hash, Hasher};
@@ -91,7 +97,7 @@ where
     }
 }
 
-impl<'de, T, const N: usize> Deserialize<'de> for Vec<T, N>
+impl<'de, T, L: LenType, const N: usize> Deserialize<'de> for Vec<T, N, L>
 where
     T: Deserialize<'de>,
 {
@@ -99,13 +105,13 @@ where
     where
         D: Deserializer<'de>,
     {
-        struct ValueVisitor<'de, T, const N: usize>(PhantomData<(&'de (), T)>);
+        struct ValueVisitor<'de, T, L, const N: usize>(PhantomData<(&'de (), T, L)>);
 
-        impl<'de, T, const N: usize> serde::de::Visitor<'de> for ValueVisitor<'de, T, N>
+        impl<'de, T, L: LenType, const N: usize> serde::de::Visitor<'de> for ValueVisitor<'de, T, L, N>
         where
             T: Deserialize<'de>,
         {
-            type Value = Vec<T, N>;
+            type Value = Vec<T, N, L>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
                 formatter.write_str("a sequence")
@@ -130,6 +136,44 @@ where
     }
 }
 
+impl<'de, T, const N: usize> Deserialize<'de> for HistoryBuffer<T, N>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ValueVisitor<'de, T, const N: usize>(PhantomData<(&'de (), T)>);
+
+        impl<'de, T, const N: usize> serde::de::Visitor<'de> for ValueVisitor<'de, T, N>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = HistoryBuffer<T, N>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut values = HistoryBuffer::new();
+
+                // NOTE the elements are written oldest first, so only the last `N` are kept
+                while let Some(value) = seq.next_element()? {
+                    values.write(value);
+                }
+
+                Ok(values)
+            }
+        }
+        deserializer.deserialize_seq(ValueVisitor(PhantomData))
+    }
+}
+
 // Dictionaries
 
 impl<'de, K, V, S, const N: usize> Deserialize<'de> for IndexMap<K, V, BuildHasherDefault<S>, N>
@@ -218,15 +262,15 @@ where
 
 // String containers
 
-impl<'de, const N: usize> Deserialize<'de> for String<N> {
+impl<'de, L: LenType, const N: usize> Deserialize<'de> for String<N, L> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct ValueVisitor<'de, const N: usize>(PhantomData<&'de ()>);
+        struct ValueVisitor<'de, L, const N: usize>(PhantomData<(&'de (), L)>);
 
-        impl<'de, const N: usize> de::Visitor<'de> for ValueVisitor<'de, N> {
-            type Value = String<N>;
+        impl<'de, L: LenType, const N: usize> de::Visitor<'de> for ValueVisitor<'de, L, N> {
+            type Value = String<N, L>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
                 write!(formatter, "a string no more than {} bytes long", N as u64)
@@ -258,6 +302,179 @@ impl<'de, const N: usize> Deserialize<'de> for String<N> {
             }
         }
 
-        deserializer.deserialize_str(ValueVisitor::<'de, N>(PhantomData))
+        deserializer.deserialize_str(ValueVisitor::<'de, L, N>(PhantomData))
+    }
+}
+
+/// Deserializes a `String` or a `Vec` by dropping the data that doesn't fit, instead of failing
+///
+/// Strings are truncated at a `char` boundary, and the elements of sequences that don't fit are
+/// skipped. Meant to be used with `#[serde(with = "heapless::de::truncate")]`, e.g. for tolerant
+/// parsing of configuration files; serialization is unaffected.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{String, Vec};
+/// use serde_derive::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(with = "heapless::de::truncate")]
+///     name: String<8>,
+///     #[serde(deserialize_with = "heapless::de::truncate::deserialize")]
+///     ports: Vec<u16, 2>,
+/// }
+/// # let _ = |c: Config| (c.name, c.ports);
+/// ```
+pub mod truncate {
+    use crate::sealed::de::Truncate;
+    use serde::{Deserializer, Serialize, Serializer};
+
+    /// Deserializes `T`, dropping the data that doesn't fit
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Truncate<'de>,
+    {
+        T::deserialize_truncating(deserializer)
+    }
+
+    /// Serializes `value` as usual, so that this module can be used with `#[serde(with)]`
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize + ?Sized,
+        S: Serializer,
+    {
+        value.serialize(serializer)
+    }
+}
+
+impl<'de, T, L: LenType, const N: usize> Truncate<'de> for Vec<T, N, L>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize_truncating<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ValueVisitor<'de, T, L, const N: usize>(PhantomData<(&'de (), T, L)>);
+
+        impl<'de, T, L: LenType, const N: usize> de::Visitor<'de> for ValueVisitor<'de, T, L, N>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = Vec<T, N, L>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut values = Vec::new();
+
+                while values.len() < N {
+                    match seq.next_element()? {
+                        // NOTE(unsafe) `values` is not full
+                        Some(value) => unsafe { values.push_unchecked(value) },
+                        None => return Ok(values),
+                    }
+                }
+
+                // skip the elements that don't fit
+                while seq.next_element::<de::IgnoredAny>()?.is_some() {}
+
+                Ok(values)
+            }
+        }
+        deserializer.deserialize_seq(ValueVisitor(PhantomData))
+    }
+}
+
+impl<'de, L: LenType, const N: usize> Truncate<'de> for String<N, L> {
+    fn deserialize_truncating<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ValueVisitor<'de, L, const N: usize>(PhantomData<(&'de (), L)>);
+
+        impl<'de, L: LenType, const N: usize> de::Visitor<'de> for ValueVisitor<'de, L, N> {
+            type Value = String<N, L>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let end = floor_char_boundary(v, N);
+
+                let mut s = String::new();
+                // NOTE(unwrap) `end <= N`
+                s.push_str(&v[..end]).unwrap();
+                Ok(s)
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let v = core::str::from_utf8(v)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Bytes(v), &self))?;
+                self.visit_str(v)
+            }
+        }
+
+        deserializer.deserialize_str(ValueVisitor::<'de, L, N>(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::truncate;
+    use crate::{String, Vec};
+    use serde::de::value::{Error, SeqDeserializer, StrDeserializer};
+
+    #[test]
+    fn truncate_string() {
+        let s: String<4> = truncate::deserialize(StrDeserializer::<Error>::new("ab")).unwrap();
+        assert_eq!(s, "ab");
+
+        let s: String<4> = truncate::deserialize(StrDeserializer::<Error>::new("abcdef")).unwrap();
+        assert_eq!(s, "abcd");
+
+        // doesn't split the 2-byte `é`
+        let s: String<4> =
+            truncate::deserialize(StrDeserializer::<Error>::new("abc\u{e9}")).unwrap();
+        assert_eq!(s, "abc");
+
+        let s: String<4, u8> =
+            truncate::deserialize(StrDeserializer::<Error>::new("abcdef")).unwrap();
+        assert_eq!(s, "abcd");
+    }
+
+    #[test]
+    fn truncate_vec() {
+        let v: Vec<u8, 4> =
+            truncate::deserialize(SeqDeserializer::<_, Error>::new([1u8, 2].iter().copied()))
+                .unwrap();
+        assert_eq!(v, [1, 2]);
+
+        let v: Vec<u8, 2> = truncate::deserialize(SeqDeserializer::<_, Error>::new(
+            [1u8, 2, 3, 4].iter().copied(),
+        ))
+        .unwrap();
+        assert_eq!(v, [1, 2]);
+
+        let v: Vec<u8, 2, u8> = truncate::deserialize(SeqDeserializer::<_, Error>::new(
+            [1u8, 2, 3].iter().copied(),
+        ))
+        .unwrap();
+        assert_eq!(v, [1, 2]);
     }
 }
@@ -0,0 +1,117 @@
+use crate::{
+    histbuf::Summary, sealed::vec::LenType, string::String, vec::Vec, IndexMap, LinearMap,
+};
+use ::defmt::{Format, Formatter};
+use hash32::{BuildHasher, Hash};
+
+impl<T> Format for Summary<'_, T>
+where
+    T: Format,
+{
+    fn format(&self, f: Formatter<'_>) {
+        ::defmt::write!(f, "len={=usize} capacity={=usize}", self.len, self.capacity);
+        if let Some(recent) = self.recent {
+            ::defmt::write!(f, " recent={}", recent);
+        }
+        if let Some(mean) = &self.mean {
+            ::defmt::write!(f, " mean={}", mean);
+        }
+    }
+}
+
+impl<L: LenType, const N: usize> Format for String<N, L> {
+    fn format(&self, f: Formatter<'_>) {
+        ::defmt::write!(f, "{=str}", self.as_str());
+    }
+}
+
+impl<T, L: LenType, const N: usize> Format for Vec<T, N, L>
+where
+    T: Format,
+{
+    fn format(&self, f: Formatter<'_>) {
+        ::defmt::write!(f, "{=[?]}", self.as_slice());
+    }
+}
+
+impl<K, V, S, const N: usize> Format for IndexMap<K, V, S, N>
+where
+    K: Eq + Hash + Format,
+    V: Format,
+    S: BuildHasher,
+{
+    fn format(&self, f: Formatter<'_>) {
+        format_map(f, self.iter());
+    }
+}
+
+impl<K, V, const N: usize> Format for LinearMap<K, V, N>
+where
+    K: Eq + Format,
+    V: Format,
+{
+    fn format(&self, f: Formatter<'_>) {
+        format_map(f, self.iter());
+    }
+}
+
+// `defmt` has no map builder, so entries are written out like `{k1: v1, k2: v2}`
+fn format_map<'a, K, V>(f: Formatter<'_>, entries: impl Iterator<Item = (&'a K, &'a V)>)
+where
+    K: Format + 'a,
+    V: Format + 'a,
+{
+    ::defmt::write!(f, "{{");
+    for (i, (key, value)) in entries.enumerate() {
+        if i != 0 {
+            ::defmt::write!(f, ", ");
+        }
+        ::defmt::write!(f, "{}: {}", key, value);
+    }
+    ::defmt::write!(f, "}}");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FnvIndexMap, HistoryBuffer, LinearMap, String, Vec};
+
+    // `defmt` needs a logger to link; the output isn't checked, this only makes sure that every
+    // `Format` implementation instantiates
+    #[::defmt::global_logger]
+    struct Logger;
+
+    unsafe impl ::defmt::Logger for Logger {
+        fn acquire() {}
+        unsafe fn flush() {}
+        unsafe fn release() {}
+        unsafe fn write(_bytes: &[u8]) {}
+    }
+
+    ::defmt::timestamp!("");
+
+    #[test]
+    fn impls() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        x.extend([1, 2, 3].iter());
+        ::defmt::info!("{}", x.summary());
+        ::defmt::info!("{}", x.numeric_summary());
+
+        let s: String<4> = String::from("ab");
+        ::defmt::info!("{}", s);
+        let s: String<4, u8> = String::from("ab");
+        ::defmt::info!("{}", s);
+
+        let v: Vec<u8, 4> = Vec::from_slice(&[1, 2]).unwrap();
+        ::defmt::info!("{}", v);
+        let v: Vec<u8, 4, u8> = Vec::from_slice(&[1, 2]).unwrap();
+        ::defmt::info!("{}", v);
+
+        let mut m: FnvIndexMap<u8, u8, 4> = FnvIndexMap::new();
+        m.insert(1, 2).unwrap();
+        ::defmt::info!("{}", m);
+
+        let mut m: LinearMap<u8, u8, 4> = LinearMap::new();
+        m.insert(1, 2).unwrap();
+        ::defmt::info!("{}", m);
+    }
+}
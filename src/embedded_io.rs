@@ -0,0 +1,97 @@
+use crate::{sealed::vec::LenType, vec::Vec};
+use embedded_io::{ErrorKind, ErrorType, Read, Write};
+
+impl<L: LenType, const N: usize> ErrorType for Vec<u8, N, L> {
+    type Error = ErrorKind;
+}
+
+/// Appends bytes to the end of the `Vec`.
+///
+/// Writes are cut short once the `Vec` is full; a non-empty write into a full `Vec` fails with
+/// [`ErrorKind::WriteZero`].
+impl<L: LenType, const N: usize> Write for Vec<u8, N, L> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        let n = buf.len().min(self.capacity() - self.len());
+        if n == 0 && !buf.is_empty() {
+            return Err(ErrorKind::WriteZero);
+        }
+
+        // NOTE(unwrap) `n` bytes fit in the spare capacity
+        self.extend_from_slice(&buf[..n]).unwrap();
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Removes bytes from the front of the `Vec`, shifting the remaining bytes down.
+///
+/// An empty `Vec` is at EOF. To read the contents without consuming them, read from
+/// [`as_slice`](type.Vec.html#method.as_slice) instead, since `&[u8]` implements `Read` as a
+/// cursor over the bytes.
+impl<L: LenType, const N: usize> Read for Vec<u8, N, L> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let n = buf.len().min(self.len());
+        buf[..n].copy_from_slice(&self[..n]);
+        self.drain(..n);
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write() {
+        let mut v: Vec<u8, 4> = Vec::new();
+        assert_eq!(v.write(&[1, 2, 3]), Ok(3));
+        assert_eq!(v.write(&[4, 5]), Ok(1));
+        assert_eq!(v, [1, 2, 3, 4]);
+        assert_eq!(v.write(&[6]), Err(ErrorKind::WriteZero));
+        assert_eq!(v.write(&[]), Ok(0));
+        assert_eq!(v.flush(), Ok(()));
+    }
+
+    #[test]
+    fn write_all() {
+        let mut v: Vec<u8, 4> = Vec::new();
+        v.write_all(b"ab").unwrap();
+        assert_eq!(v, b"ab");
+        assert!(v.write_all(b"cde").is_err());
+        assert_eq!(v, b"abcd");
+    }
+
+    #[test]
+    fn write_u8_len() {
+        let mut v: Vec<u8, 4, u8> = Vec::new();
+        assert_eq!(v.write(&[1, 2, 3, 4, 5]), Ok(4));
+        let mut buf = [0; 4];
+        assert_eq!(v.read(&mut buf), Ok(4));
+        assert_eq!(buf, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn read() {
+        let mut v: Vec<u8, 4> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        let mut buf = [0; 2];
+        assert_eq!(v.read(&mut buf), Ok(2));
+        assert_eq!(buf, [1, 2]);
+        assert_eq!(v, [3]);
+        assert_eq!(v.read(&mut buf), Ok(1));
+        assert_eq!(buf[0], 3);
+        assert_eq!(v.read(&mut buf), Ok(0));
+    }
+
+    #[test]
+    fn read_slice() {
+        let v: Vec<u8, 4> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        let mut reader = v.as_slice();
+        let mut buf = [0; 3];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [1, 2, 3]);
+        assert_eq!(v, [1, 2, 3]);
+    }
+}
@@ -0,0 +1,236 @@
+//! Base64 encoding, with the standard alphabet and padding ([RFC 4648])
+//!
+//! [RFC 4648]: https://datatracker.ietf.org/doc/html/rfc4648#section-4
+//!
+//! # Examples
+//!
+//! ```
+//! use heapless::{encoding::base64, String, Vec};
+//!
+//! let key: String<8> = base64::encode_array(b"key");
+//! assert_eq!(key, "a2V5");
+//!
+//! let bytes: Vec<u8, 4> = base64::decode(b"aGk=").unwrap();
+//! assert_eq!(bytes, *b"hi");
+//! ```
+
+use super::DecodeError;
+use crate::{sealed::vec::LenType, vec::CapacityError, String, Vec};
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+const PAD: u8 = b'=';
+
+struct Assert<const M: usize, const N: usize>;
+
+impl<const M: usize, const N: usize> Assert<M, N> {
+    const FITS: () = assert!(N / 4 * 3 >= M, "the encoded bytes don't fit");
+}
+
+/// Returns the length of the encoding of `len` bytes, including the padding
+pub const fn encoded_len(len: usize) -> usize {
+    // the last group is padded
+    match len % 3 {
+        0 => len / 3 * 4,
+        _ => (len / 3 + 1) * 4,
+    }
+}
+
+/// Encodes `bytes` into a new `String`
+///
+/// Returns an error if the encoded bytes don't fit.
+pub fn encode<const N: usize>(bytes: &[u8]) -> Result<String<N>, CapacityError> {
+    let mut s = String::new();
+    encode_to_string(bytes, &mut s)?;
+    Ok(s)
+}
+
+/// Encodes the array `bytes` into a new `String`, checking at compile time that it fits
+///
+/// # Examples
+///
+/// ``` compile_fail
+/// use heapless::{encoding::base64, String};
+///
+/// // 4 bytes need 8 characters, as the encoding is padded
+/// let s: String<6> = base64::encode_array(&[0; 4]);
+/// ```
+pub fn encode_array<const M: usize, const N: usize>(bytes: &[u8; M]) -> String<N> {
+    let () = Assert::<M, N>::FITS;
+
+    let mut s = String::new();
+    // NOTE(unwrap) checked above
+    encode_to_string(bytes, &mut s).unwrap();
+    s
+}
+
+/// Appends the encoding of `bytes` to `s`
+///
+/// Returns an error, leaving `s` untouched, if the encoded bytes don't fit.
+pub fn encode_to_string<L: LenType, const N: usize>(
+    bytes: &[u8],
+    s: &mut String<N, L>,
+) -> Result<(), CapacityError> {
+    if bytes.len() > s.remaining_capacity() / 4 * 3 {
+        return Err(CapacityError);
+    }
+
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let mut quad = [
+            ALPHABET[usize::from(b[0] >> 2)],
+            ALPHABET[usize::from((b[0] & 0x03) << 4 | b[1] >> 4)],
+            ALPHABET[usize::from((b[1] & 0x0f) << 2 | b[2] >> 6)],
+            ALPHABET[usize::from(b[2] & 0x3f)],
+        ];
+        for c in &mut quad[chunk.len() + 1..] {
+            *c = PAD;
+        }
+        // NOTE(unsafe) the alphabet and the padding are ASCII
+        // NOTE(unwrap) there's room for the whole encoding
+        unsafe { s.as_mut_vec() }.extend_from_slice(&quad).unwrap();
+    }
+
+    Ok(())
+}
+
+/// Decodes padded `input` into a new `Vec`
+///
+/// Returns an error if `input` isn't Base64 encoded or if the decoded bytes don't fit.
+pub fn decode<const N: usize>(input: &[u8]) -> Result<Vec<u8, N>, DecodeError> {
+    let mut v = Vec::new();
+    decode_to_vec(input, &mut v)?;
+    Ok(v)
+}
+
+/// Appends the decoding of padded `input` to `v`
+///
+/// Returns an error, leaving `v` untouched, if `input` isn't Base64 encoded or if the decoded
+/// bytes don't fit.
+pub fn decode_to_vec<L: LenType, const N: usize>(
+    input: &[u8],
+    v: &mut Vec<u8, N, L>,
+) -> Result<(), DecodeError> {
+    if !input.chunks_exact(4).remainder().is_empty() {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    let padding = input.iter().rev().take(2).filter(|&&c| c == PAD).count();
+    if input.len() / 4 * 3 - padding > v.remaining_capacity() {
+        return Err(DecodeError::Capacity(CapacityError));
+    }
+
+    let data = &input[..input.len() - padding];
+    let len = v.len();
+    for (i, chunk) in data.chunks(4).enumerate() {
+        let mut sextets = [0; 4];
+        for (j, &c) in chunk.iter().enumerate() {
+            sextets[j] = match sextet(c) {
+                Some(sextet) => sextet,
+                None => {
+                    v.truncate(len);
+                    return Err(DecodeError::InvalidByte {
+                        position: 4 * i + j,
+                    });
+                }
+            };
+        }
+
+        let bytes = [
+            sextets[0] << 2 | sextets[1] >> 4,
+            sextets[1] << 4 | sextets[2] >> 2,
+            sextets[2] << 6 | sextets[3],
+        ];
+        for &byte in &bytes[..chunk.len() - 1] {
+            // NOTE(unsafe) there's room for all the decoded bytes
+            unsafe { v.push_unchecked(byte) };
+        }
+    }
+
+    Ok(())
+}
+
+fn sextet(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, decode_to_vec, encode, encode_array, encode_to_string, encoded_len};
+    use crate::{encoding::DecodeError, vec::CapacityError, String, Vec};
+
+    // test vectors from RFC 4648
+    const VECTORS: &[(&[u8], &str)] = &[
+        (b"", ""),
+        (b"f", "Zg=="),
+        (b"fo", "Zm8="),
+        (b"foo", "Zm9v"),
+        (b"foob", "Zm9vYg=="),
+        (b"fooba", "Zm9vYmE="),
+        (b"foobar", "Zm9vYmFy"),
+    ];
+
+    #[test]
+    fn encode_() {
+        for &(bytes, encoded) in VECTORS {
+            assert_eq!(encode::<8>(bytes).unwrap(), encoded);
+            assert_eq!(encoded_len(bytes.len()), encoded.len());
+        }
+        assert_eq!(encode::<7>(b"foob"), Err(CapacityError));
+
+        let s: String<8> = encode_array(&[0xfb, 0xff]);
+        assert_eq!(s, "+/8=");
+
+        let mut s: String<5> = String::from("x");
+        assert_eq!(encode_to_string(b"foob", &mut s), Err(CapacityError));
+        assert_eq!(s, "x");
+        encode_to_string(b"foo", &mut s).unwrap();
+        assert_eq!(s, "xZm9v");
+    }
+
+    #[test]
+    fn decode_() {
+        for &(bytes, encoded) in VECTORS {
+            assert_eq!(decode::<6>(encoded.as_bytes()).unwrap(), bytes);
+        }
+        assert_eq!(decode::<3>(b"+/8=").unwrap(), [0xfb, 0xff]);
+
+        assert_eq!(
+            decode::<3>(b"Zm9vYg=="),
+            Err(DecodeError::Capacity(CapacityError))
+        );
+        assert_eq!(decode::<8>(b"Zm9vYg="), Err(DecodeError::InvalidLength));
+        assert_eq!(
+            decode::<8>(b"Zm=v"),
+            Err(DecodeError::InvalidByte { position: 2 })
+        );
+        assert_eq!(
+            decode::<8>(b"Zm9vY==="),
+            Err(DecodeError::InvalidByte { position: 5 })
+        );
+
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[7]).unwrap();
+        assert_eq!(
+            decode_to_vec(b"Zm9v Zm9v", &mut v),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            decode_to_vec(b"Zm9v*m9v", &mut v),
+            Err(DecodeError::InvalidByte { position: 4 })
+        );
+        assert_eq!(v, [7]);
+        decode_to_vec(b"Zm8=", &mut v).unwrap();
+        assert_eq!(v, [7, b'f', b'o']);
+    }
+}
@@ -0,0 +1,181 @@
+//! Lowercase hexadecimal encoding
+//!
+//! # Examples
+//!
+//! ```
+//! use heapless::{encoding::hex, String, Vec};
+//!
+//! let id: String<8> = hex::encode_array(&[0xde, 0xad, 0xbe, 0xef]);
+//! assert_eq!(id, "deadbeef");
+//!
+//! let bytes: Vec<u8, 4> = hex::decode(b"DEADbeef").unwrap();
+//! assert_eq!(bytes, [0xde, 0xad, 0xbe, 0xef]);
+//! ```
+
+use super::DecodeError;
+use crate::{sealed::vec::LenType, vec::CapacityError, String, Vec};
+
+const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+struct Assert<const M: usize, const N: usize>;
+
+impl<const M: usize, const N: usize> Assert<M, N> {
+    const FITS: () = assert!(N / 2 >= M, "the encoded bytes don't fit");
+}
+
+/// Encodes `bytes` into a new `String`
+///
+/// Returns an error if the encoded bytes, two characters per byte, don't fit.
+pub fn encode<const N: usize>(bytes: &[u8]) -> Result<String<N>, CapacityError> {
+    let mut s = String::new();
+    encode_to_string(bytes, &mut s)?;
+    Ok(s)
+}
+
+/// Encodes the array `bytes` into a new `String`, checking at compile time that it fits
+///
+/// # Examples
+///
+/// ``` compile_fail
+/// use heapless::{encoding::hex, String};
+///
+/// // 4 bytes need 8 characters
+/// let s: String<7> = hex::encode_array(&[0; 4]);
+/// ```
+pub fn encode_array<const M: usize, const N: usize>(bytes: &[u8; M]) -> String<N> {
+    let () = Assert::<M, N>::FITS;
+
+    let mut s = String::new();
+    // NOTE(unwrap) checked above
+    encode_to_string(bytes, &mut s).unwrap();
+    s
+}
+
+/// Appends the encoding of `bytes` to `s`
+///
+/// Returns an error, leaving `s` untouched, if the encoded bytes don't fit.
+pub fn encode_to_string<L: LenType, const N: usize>(
+    bytes: &[u8],
+    s: &mut String<N, L>,
+) -> Result<(), CapacityError> {
+    if bytes.len() > s.remaining_capacity() / 2 {
+        return Err(CapacityError);
+    }
+
+    for byte in bytes {
+        let digits = [
+            DIGITS[usize::from(byte >> 4)],
+            DIGITS[usize::from(byte & 0xf)],
+        ];
+        // NOTE(unsafe) the digits are ASCII
+        // NOTE(unwrap) there's room for all the digits
+        unsafe { s.as_mut_vec() }
+            .extend_from_slice(&digits)
+            .unwrap();
+    }
+
+    Ok(())
+}
+
+/// Decodes `input`, in either case, into a new `Vec`
+///
+/// Returns an error if `input` isn't hex encoded or if the decoded bytes don't fit.
+pub fn decode<const N: usize>(input: &[u8]) -> Result<Vec<u8, N>, DecodeError> {
+    let mut v = Vec::new();
+    decode_to_vec(input, &mut v)?;
+    Ok(v)
+}
+
+/// Appends the decoding of `input`, in either case, to `v`
+///
+/// Returns an error, leaving `v` untouched, if `input` isn't hex encoded or if the decoded bytes
+/// don't fit.
+pub fn decode_to_vec<L: LenType, const N: usize>(
+    input: &[u8],
+    v: &mut Vec<u8, N, L>,
+) -> Result<(), DecodeError> {
+    if input.len() % 2 == 1 {
+        return Err(DecodeError::InvalidLength);
+    }
+    if input.len() / 2 > v.remaining_capacity() {
+        return Err(DecodeError::Capacity(CapacityError));
+    }
+
+    let len = v.len();
+    for (i, pair) in input.chunks_exact(2).enumerate() {
+        let byte = match (digit(pair[0]), digit(pair[1])) {
+            (Some(hi), Some(lo)) => hi << 4 | lo,
+            (None, _) => return Err(invalid_byte(v, len, 2 * i)),
+            (_, None) => return Err(invalid_byte(v, len, 2 * i + 1)),
+        };
+        // NOTE(unsafe) there's room for all the decoded bytes
+        unsafe { v.push_unchecked(byte) };
+    }
+
+    Ok(())
+}
+
+fn digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+// undoes a partial decoding
+fn invalid_byte<L: LenType, const N: usize>(
+    v: &mut Vec<u8, N, L>,
+    len: usize,
+    position: usize,
+) -> DecodeError {
+    v.truncate(len);
+    DecodeError::InvalidByte { position }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, decode_to_vec, encode, encode_array, encode_to_string};
+    use crate::{encoding::DecodeError, vec::CapacityError, String, Vec};
+
+    #[test]
+    fn encode_() {
+        assert_eq!(encode::<0>(&[]).unwrap(), "");
+        assert_eq!(encode::<4>(&[0x01, 0xff]).unwrap(), "01ff");
+        assert_eq!(encode::<3>(&[0x01, 0xff]), Err(CapacityError));
+
+        let s: String<6> = encode_array(&[0x0a, 0xb0, 0x00]);
+        assert_eq!(s, "0ab000");
+
+        let mut s: String<5> = String::from("x");
+        assert_eq!(encode_to_string(&[1, 2, 3], &mut s), Err(CapacityError));
+        assert_eq!(s, "x");
+        encode_to_string(&[1, 2], &mut s).unwrap();
+        assert_eq!(s, "x0102");
+    }
+
+    #[test]
+    fn decode_() {
+        assert_eq!(decode::<0>(b"").unwrap(), []);
+        assert_eq!(decode::<2>(b"01Ff").unwrap(), [0x01, 0xff]);
+        assert_eq!(
+            decode::<1>(b"01ff"),
+            Err(DecodeError::Capacity(CapacityError))
+        );
+        assert_eq!(decode::<2>(b"01f"), Err(DecodeError::InvalidLength));
+        assert_eq!(
+            decode::<2>(b"01fg"),
+            Err(DecodeError::InvalidByte { position: 3 })
+        );
+
+        let mut v: Vec<u8, 4> = Vec::from_slice(&[7]).unwrap();
+        assert_eq!(
+            decode_to_vec(b"0102 3", &mut v),
+            Err(DecodeError::InvalidByte { position: 4 })
+        );
+        assert_eq!(v, [7]);
+        decode_to_vec(b"0102", &mut v).unwrap();
+        assert_eq!(v, [7, 1, 2]);
+    }
+}
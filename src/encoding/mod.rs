@@ -0,0 +1,46 @@
+//! Hex and Base64 encoding into fixed capacity containers
+//!
+//! The encoders append ASCII text to a [`String`](crate::String), and the decoders append bytes to
+//! a [`Vec`](crate::Vec). Where the length of the input is known at compile time, the
+//! `encode_array` functions check that the output fits at compile time too.
+//!
+//! This module requires the `encoding` feature.
+
+use core::fmt;
+
+use crate::vec::CapacityError;
+
+pub mod base64;
+pub mod hex;
+
+/// The error returned when decoding fails
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The decoded bytes don't fit
+    Capacity(CapacityError),
+    /// The input has a length that no encoded data has
+    InvalidLength,
+    /// The input contains an unexpected byte at `position`
+    InvalidByte {
+        /// The position of the first unexpected byte
+        position: usize,
+    },
+}
+
+impl From<CapacityError> for DecodeError {
+    fn from(e: CapacityError) -> Self {
+        DecodeError::Capacity(e)
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Capacity(e) => e.fmt(f),
+            DecodeError::InvalidLength => f.write_str("invalid input length"),
+            DecodeError::InvalidByte { position } => {
+                write!(f, "invalid byte found at position {}", position)
+            }
+        }
+    }
+}
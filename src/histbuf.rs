@@ -1,7 +1,17 @@
-use core::mem::MaybeUninit;
+//! A fixed capacity history buffer, similar to a write-only ring buffer.
+//!
+//! See [`HistoryBuffer`](struct.HistoryBuffer.html) for details.
+
+use core::cmp;
+use core::fmt;
+use core::iter;
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::ops;
 use core::ptr;
 use core::slice;
 
+use crate::{sealed::histbuf::Mean, Vec};
+
 /// A "history buffer", similar to a write-only ring buffer of fixed length.
 ///
 /// This buffer keeps a fixed number of elements.  On write, the oldest element
@@ -28,6 +38,9 @@ use core::slice;
 /// // To access all elements in an unspecified order, use `as_slice()`.
 /// for el in buf.as_slice() { println!("{:?}", el); }
 ///
+/// // To access all elements in chronological order, use `oldest_ordered()`.
+/// for el in buf.oldest_ordered() { println!("{:?}", el); }
+///
 /// // Now we can prepare an average of all values, which comes out to 4.
 /// let avg = buf.as_slice().iter().sum::<usize>() / buf.len();
 /// assert_eq!(avg, 4);
@@ -68,6 +81,132 @@ impl<T, const N: usize> HistoryBuffer<T, N> {
     pub fn clear(&mut self) {
         *self = Self::new();
     }
+
+    /// Empties the buffer *without* dropping its elements.
+    ///
+    /// This is meant for the case where the elements have already been moved out of the buffer
+    /// through raw pointers, e.g. with `ptr::read` on the pointer returned by
+    /// `as_slice().as_ptr()`, and must not be dropped a second time.
+    ///
+    /// # Safety
+    ///
+    /// Every element in the buffer must already have been moved out or otherwise logically
+    /// consumed, as they will no longer be dropped by the buffer. Calling this method while the
+    /// buffer still owns its elements leaks them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ptr;
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3].iter());
+    ///
+    /// let mut out = [0; 4];
+    /// let len = x.len();
+    /// unsafe {
+    ///     ptr::copy_nonoverlapping(x.as_slice().as_ptr(), out.as_mut_ptr(), len);
+    ///     x.assume_empty();
+    /// }
+    ///
+    /// assert_eq!(out, [1, 2, 3, 0]);
+    /// assert_eq!(x.len(), 0);
+    /// ```
+    pub unsafe fn assume_empty(&mut self) {
+        self.write_at = 0;
+        self.filled = false;
+    }
+
+    /// Decomposes the buffer into its raw parts: the backing array, the index the next element
+    /// will be written at, and whether the buffer is full.
+    ///
+    /// If the buffer is full every element of the array is initialized and the oldest one is at
+    /// the write index. Otherwise only the elements before the write index are initialized, oldest
+    /// first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4, 5].iter());
+    ///
+    /// let (data, write_at, filled) = x.into_parts();
+    /// assert_eq!(write_at, 1);
+    /// assert!(filled);
+    /// assert_eq!(unsafe { data[write_at].assume_init() }, 2);
+    /// ```
+    pub fn into_parts(self) -> ([MaybeUninit<T>; N], usize, bool) {
+        let this = ManuallyDrop::new(self);
+        // NOTE(unsafe) `this` is never used again, so the elements are moved rather than copied
+        (unsafe { ptr::read(&this.data) }, this.write_at, this.filled)
+    }
+
+    /// Creates a buffer from raw parts, as returned by [`into_parts`](#method.into_parts).
+    ///
+    /// # Safety
+    ///
+    /// - `write_at` must be less than `N`, or equal to 0 if `N` is 0.
+    /// - If `filled` is `true`, every element of `data` must be initialized.
+    /// - If `filled` is `false`, the elements of `data` before `write_at` must be initialized.
+    ///
+    /// The buffer takes ownership of the initialized elements and will drop them; any other
+    /// element of `data` is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::mem::MaybeUninit;
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let data = [MaybeUninit::new(1), MaybeUninit::new(2), MaybeUninit::uninit()];
+    /// let x: HistoryBuffer<u8, 3> = unsafe { HistoryBuffer::from_parts(data, 2, false) };
+    /// assert_eq!(x.as_slice(), [1, 2]);
+    /// ```
+    pub unsafe fn from_parts(data: [MaybeUninit<T>; N], write_at: usize, filled: bool) -> Self {
+        debug_assert!(write_at < N || (N == 0 && write_at == 0));
+
+        Self {
+            data,
+            write_at,
+            filled,
+        }
+    }
+
+    /// Makes a buffer whose write position is out of bounds usable again, returning whether
+    /// anything had to be changed.
+    ///
+    /// This is a best-effort recovery routine for buffers whose internal state may have been
+    /// corrupted, e.g. by a faulty FFI or DMA transfer or by memory disturbances in the field. An
+    /// out of bounds write position is clamped to the last slot of the backing array and the fill
+    /// state is kept, so the buffer keeps every element it held as long as only the write position
+    /// was corrupted; the chronological order of a full buffer is then unspecified. It is not a
+    /// substitute for upholding the safety contracts of the `unsafe` methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3].iter());
+    ///
+    /// // a consistent buffer is left alone
+    /// assert!(!x.repair());
+    /// assert_eq!(x.len(), 3);
+    /// ```
+    pub fn repair(&mut self) -> bool {
+        if self.write_at < N || (N == 0 && self.write_at == 0) {
+            false
+        } else {
+            // NOTE the elements before the clamped position were already claimed to be
+            // initialized, so this can't expose uninitialized slots
+            self.write_at = N.saturating_sub(1);
+            true
+        }
+    }
 }
 
 impl<T, const N: usize> HistoryBuffer<T, N>
@@ -85,9 +224,13 @@ where
     /// let mut x: HistoryBuffer<u8, 16> = HistoryBuffer::new_with(4);
     /// // All elements are four
     /// assert_eq!(x.as_slice(), [4; 16]);
+    ///
+    /// // The construction also works in `const` contexts
+    /// static FILTER: HistoryBuffer<i16, 32> = HistoryBuffer::new_with(0);
+    /// assert_eq!(FILTER.recent(), Some(&0));
     /// ```
     #[inline]
-    pub fn new_with(t: T) -> Self {
+    pub const fn new_with(t: T) -> Self {
         Self {
             data: [MaybeUninit::new(t); N],
             write_at: 0,
@@ -99,12 +242,110 @@ where
     pub fn clear_with(&mut self, t: T) {
         *self = Self::new_with(t);
     }
+
+    /// Returns a snapshot of the buffer, which can later be passed to
+    /// [`restore`](#method.restore) to roll the buffer back to its current state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3].iter());
+    ///
+    /// let snapshot = x.checkpoint();
+    /// x.extend([4, 5, 6].iter());
+    /// assert_eq!(x.recent(), Some(&6));
+    ///
+    /// x.restore(&snapshot);
+    /// assert_eq!(x.as_slice(), [1, 2, 3]);
+    /// ```
+    pub fn checkpoint(&self) -> Self {
+        Self {
+            data: self.data,
+            write_at: self.write_at,
+            filled: self.filled,
+        }
+    }
+
+    /// Rolls the buffer back to the state captured by [`checkpoint`](#method.checkpoint).
+    pub fn restore(&mut self, snapshot: &Self) {
+        self.data = snapshot.data;
+        self.write_at = snapshot.write_at;
+        self.filled = snapshot.filled;
+    }
+
+    /// Returns a copy of the buffer with every element multiplied by `gain`.
+    ///
+    /// The copy has the same fill level and chronological order as the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+    /// x.extend([1, -2, 3].iter());
+    ///
+    /// let y = x.scaled(10);
+    /// assert_eq!(y.as_slice(), [10, -20, 30]);
+    /// ```
+    pub fn scaled(&self, gain: T) -> Self
+    where
+        T: ops::Mul<Output = T>,
+    {
+        let mut out = self.checkpoint();
+        let len = out.len();
+
+        for slot in &mut out.data[..len] {
+            // NOTE(unsafe) the first `len` slots are initialized
+            let el = unsafe { *slot.as_ptr() };
+            *slot = MaybeUninit::new(el * gain);
+        }
+
+        out
+    }
+
+    /// Copies and writes all elements in a slice to the buffer, like
+    /// [`extend_from_slice`](#method.extend_from_slice).
+    ///
+    /// Only the last `N` elements of the slice are written, with at most two bulk copies instead
+    /// of one write per element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend_from_copy_slice(&[1, 2, 3]);
+    /// x.extend_from_copy_slice(&[4, 5]);
+    /// assert_eq!(x.as_slice(), [5, 2, 3, 4]);
+    /// ```
+    pub fn extend_from_copy_slice(&mut self, other: &[T]) {
+        let other = &other[other.len().saturating_sub(N)..];
+        let first = cmp::min(other.len(), N - self.write_at);
+
+        // NOTE(unsafe) `T: Copy`, so the overwritten elements don't need to be dropped
+        unsafe {
+            let data = self.data.as_mut_ptr() as *mut T;
+            ptr::copy_nonoverlapping(other.as_ptr(), data.add(self.write_at), first);
+            ptr::copy_nonoverlapping(other.as_ptr().add(first), data, other.len() - first);
+        }
+
+        self.write_at += other.len();
+        if self.write_at >= N && N != 0 {
+            self.write_at -= N;
+            self.filled = true;
+        }
+    }
 }
 
 impl<T, const N: usize> HistoryBuffer<T, N> {
     /// Returns the current fill level of the buffer.
     #[inline]
-    pub fn len(&self) -> usize {
+    pub const fn len(&self) -> usize {
         if self.filled {
             N
         } else {
@@ -119,19 +360,108 @@ impl<T, const N: usize> HistoryBuffer<T, N> {
         N
     }
 
+    /// Returns `true` if the buffer holds at least `min` elements.
+    ///
+    /// Useful to tell whether enough samples have been collected for statistics over the buffer
+    /// to be meaningful, e.g. at the end of a filter's warm-up period.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 8> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3].iter());
+    /// assert!(x.has_min_samples(3));
+    /// assert!(!x.has_min_samples(4));
+    /// ```
+    #[inline]
+    pub const fn has_min_samples(&self, min: usize) -> bool {
+        self.len() >= min
+    }
+
     /// Writes an element to the buffer, overwriting the oldest value.
     pub fn write(&mut self, t: T) {
-        if self.filled {
-            // Drop the old before we overwrite it.
-            unsafe { ptr::drop_in_place(self.data[self.write_at].as_mut_ptr()) }
-        }
-        self.data[self.write_at] = MaybeUninit::new(t);
+        // NOTE the old value is only dropped once the buffer is consistent again, so that a
+        // panic in its destructor can't lead to a double drop
+        drop(self.write_evict(t));
+    }
+
+    /// Writes an element to the buffer, and returns the oldest value if it was overwritten.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 2> = HistoryBuffer::new();
+    /// assert_eq!(x.write_evict(1), None);
+    /// assert_eq!(x.write_evict(2), None);
+    /// assert_eq!(x.write_evict(3), Some(1));
+    /// ```
+    pub fn write_evict(&mut self, t: T) -> Option<T> {
+        let old = mem::replace(&mut self.data[self.write_at], MaybeUninit::new(t));
+        let evicted = self.filled;
 
         self.write_at += 1;
         if self.write_at == self.capacity() {
             self.write_at = 0;
             self.filled = true;
         }
+
+        if evicted {
+            Some(unsafe { old.assume_init() })
+        } else {
+            None
+        }
+    }
+
+    /// Writes an element to the buffer, and calls `f` with the oldest value if it was overwritten.
+    ///
+    /// Unlike the hook of a [`HookedHistoryBuffer`](struct.HookedHistoryBuffer.html), `f` can
+    /// capture state, e.g. a long-term accumulator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u32, 2> = HistoryBuffer::new();
+    /// let mut total = 0;
+    /// for i in 1..=5 {
+    ///     x.write_with(i, |old| total += old);
+    /// }
+    /// assert_eq!(total, 1 + 2 + 3);
+    /// ```
+    pub fn write_with<F>(&mut self, t: T, f: F)
+    where
+        F: FnOnce(T),
+    {
+        if let Some(old) = self.write_evict(t) {
+            f(old);
+        }
+    }
+
+    /// Writes an element to the buffer like [`write`](#method.write), and returns `true` if this
+    /// write filled the buffer.
+    ///
+    /// Only the write that makes the buffer full returns `true`; later writes to the full buffer
+    /// return `false`, until the buffer is cleared and filled again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 2> = HistoryBuffer::new();
+    /// assert!(!x.write_notify_full(1));
+    /// assert!(x.write_notify_full(2));
+    /// assert!(!x.write_notify_full(3));
+    /// ```
+    pub fn write_notify_full(&mut self, t: T) -> bool {
+        let was_filled = self.filled;
+        self.write(t);
+        !was_filled && self.filled
     }
 
     /// Clones and writes all elements in a slice to the buffer.
@@ -147,6 +477,103 @@ impl<T, const N: usize> HistoryBuffer<T, N> {
         }
     }
 
+    /// Writes `value` to the buffer `count` times.
+    ///
+    /// At most `N` copies of `value` are written, as any earlier ones would be overwritten
+    /// anyway; this means that `value` is cloned at most `N - 1` times.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.write(1);
+    /// x.write_repeated(0, 2);
+    /// assert_eq!(x.as_slice(), [1, 0, 0]);
+    ///
+    /// x.write_repeated(2, 1_000_000);
+    /// assert_eq!(x.as_slice(), [2, 2, 2, 2]);
+    /// ```
+    pub fn write_repeated(&mut self, value: T, count: usize)
+    where
+        T: Clone,
+    {
+        let count = cmp::min(count, N);
+        if count == 0 {
+            return;
+        }
+
+        for _ in 1..count {
+            self.write(value.clone());
+        }
+        self.write(value);
+    }
+
+    /// Fills the free slots of a partially filled buffer with clones of the most recent element,
+    /// making the buffer full.
+    ///
+    /// Does nothing if the buffer is empty or already full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 5> = HistoryBuffer::new();
+    /// x.extend([1, 2].iter());
+    /// x.pad_with_recent();
+    /// assert_eq!(x.as_slice(), [1, 2, 2, 2, 2]);
+    /// ```
+    pub fn pad_with_recent(&mut self)
+    where
+        T: Clone,
+    {
+        if self.write_at == 0 {
+            // empty or full
+            return;
+        }
+
+        while !self.filled {
+            let recent = unsafe { (*self.data[self.write_at - 1].as_ptr()).clone() };
+            self.write(recent);
+        }
+    }
+
+    /// Fills the buffer by writing the elements of `iter` over and over, until `N` elements have
+    /// been written.
+    ///
+    /// The buffer is full afterwards, holding the elements of `iter` repeated from the oldest
+    /// element on. If `iter` yields no elements the buffer is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 7> = HistoryBuffer::new();
+    /// x.fill_cycling([1, 2, 3].iter().cloned());
+    /// assert_eq!(x.as_slice(), [1, 2, 3, 1, 2, 3, 1]);
+    /// ```
+    pub fn fill_cycling<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T> + Clone,
+    {
+        let mut written = 0;
+        while written < N {
+            let start = written;
+            for item in iter.clone().into_iter().take(N - written) {
+                self.write(item);
+                written += 1;
+            }
+
+            if written == start {
+                // the iterator is empty
+                return;
+            }
+        }
+    }
+
     /// Returns a reference to the most recently written value.
     ///
     /// # Examples
@@ -171,111 +598,4265 @@ impl<T, const N: usize> HistoryBuffer<T, N> {
         }
     }
 
-    /// Returns the array slice backing the buffer, without keeping track
-    /// of the write position. Therefore, the element order is unspecified.
-    pub fn as_slice(&self) -> &[T] {
-        unsafe { slice::from_raw_parts(self.data.as_ptr() as *const _, self.len()) }
-    }
-}
-
-impl<T, const N: usize> Extend<T> for HistoryBuffer<T, N> {
-    fn extend<I>(&mut self, iter: I)
-    where
-        I: IntoIterator<Item = T>,
-    {
-        for item in iter.into_iter() {
-            self.write(item);
+    /// Returns a reference to the element written `age` writes before the most recent one.
+    ///
+    /// Age 0 is the most recent element and age `len() - 1` is the oldest one; returns `None` if
+    /// `age` is out of range. Note that this counts in the opposite direction to the
+    /// chronological indices used by e.g. [`get_clamped`](#method.get_clamped). The buffer can
+    /// also be indexed by age with `[]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4, 5].iter());
+    ///
+    /// assert_eq!(x.get(0), Some(&5));
+    /// assert_eq!(x.get(3), Some(&2));
+    /// assert_eq!(x.get(4), None);
+    /// assert_eq!(x[1], 4);
+    ///
+    /// // a difference filter, x[t] - x[t - 3]
+    /// let diff = x.get(0).zip(x.get(3)).map(|(a, b)| a - b);
+    /// assert_eq!(diff, Some(3));
+    /// ```
+    pub fn get(&self, age: usize) -> Option<&T> {
+        let len = self.len();
+        if age < len {
+            Some(unsafe { self.get_ordered_unchecked(len - 1 - age) })
+        } else {
+            None
         }
     }
-}
 
-impl<'a, T, const N: usize> Extend<&'a T> for HistoryBuffer<T, N>
-where
-    T: 'a + Clone,
-{
-    fn extend<I>(&mut self, iter: I)
-    where
-        I: IntoIterator<Item = &'a T>,
-    {
-        self.extend(iter.into_iter().cloned())
+    /// Returns a mutable reference to the most recently written value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 16> = HistoryBuffer::new();
+    /// x.write(4);
+    /// x.write(10);
+    /// *x.recent_mut().unwrap() += 1;
+    /// assert_eq!(x.recent(), Some(&11));
+    /// ```
+    pub fn recent_mut(&mut self) -> Option<&mut T> {
+        let len = self.len();
+        if len == 0 {
+            None
+        } else {
+            let idx = self.ordered_index(len - 1);
+            Some(unsafe { &mut *self.data[idx].as_mut_ptr() })
+        }
     }
-}
 
-impl<T, const N: usize> Drop for HistoryBuffer<T, N> {
-    fn drop(&mut self) {
-        unsafe {
-            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
-                self.data.as_mut_ptr() as *mut T,
+    /// Returns a reference to the oldest value in the buffer, which is the next one to be
+    /// overwritten once the buffer is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 2> = HistoryBuffer::new();
+    /// x.write(4);
+    /// assert_eq!(x.oldest(), Some(&4));
+    /// x.write(10);
+    /// x.write(12);
+    /// assert_eq!(x.oldest(), Some(&10));
+    /// ```
+    pub fn oldest(&self) -> Option<&T> {
+        if self.len() == 0 {
+            None
+        } else {
+            Some(unsafe { self.get_ordered_unchecked(0) })
+        }
+    }
+
+    /// Returns a mutable reference to the oldest value in the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 2> = HistoryBuffer::new();
+    /// x.extend([4, 10, 12].iter());
+    /// *x.oldest_mut().unwrap() = 0;
+    /// assert_eq!(x.oldest(), Some(&0));
+    /// ```
+    pub fn oldest_mut(&mut self) -> Option<&mut T> {
+        if self.len() == 0 {
+            None
+        } else {
+            let idx = self.ordered_index(0);
+            Some(unsafe { &mut *self.data[idx].as_mut_ptr() })
+        }
+    }
+
+    /// Returns a reference to the element at chronological index `index`, clamping out of range
+    /// indices to the nearest end of the buffer.
+    ///
+    /// Index 0 is the oldest element. Negative indices count back from the most recent element,
+    /// so index -1 is the element returned by [`recent`](#method.recent). Indices past the most
+    /// recent element return the most recent element, and negative indices past the oldest
+    /// element return the oldest element.
+    ///
+    /// Returns `None` only if the buffer is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3].iter());
+    ///
+    /// assert_eq!(x.get_clamped(0), Some(&1));
+    /// assert_eq!(x.get_clamped(-1), Some(&3));
+    /// assert_eq!(x.get_clamped(-2), Some(&2));
+    /// assert_eq!(x.get_clamped(10), Some(&3));
+    /// assert_eq!(x.get_clamped(-10), Some(&1));
+    /// ```
+    pub fn get_clamped(&self, index: isize) -> Option<&T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+
+        let i = if index >= 0 {
+            cmp::min(index as usize, len - 1)
+        } else {
+            // NOTE(wrapping_neg) `-isize::MIN` overflows but `as usize` gives the right magnitude
+            len.saturating_sub(index.wrapping_neg() as usize)
+        };
+
+        Some(unsafe { self.get_ordered_unchecked(i) })
+    }
+
+    /// Returns the array slice backing the buffer, without keeping track
+    /// of the write position. Therefore, the element order is unspecified.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.data.as_ptr() as *const _, self.len()) }
+    }
+
+    /// Returns the contents of the buffer as a pair of slices, which when concatenated hold the
+    /// elements in chronological order, oldest first.
+    ///
+    /// The second slice is empty until the buffer wraps around for the first time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3].iter());
+    /// assert_eq!(x.as_slices(), (&[1, 2, 3][..], &[][..]));
+    ///
+    /// x.extend([4, 5, 6].iter());
+    /// assert_eq!(x.as_slices(), (&[3, 4][..], &[5, 6][..]));
+    /// ```
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (older, newer) = if self.filled {
+            let (newer, older) = self.data.split_at(self.write_at);
+            (older, newer)
+        } else {
+            (&self.data[..self.write_at], &[][..])
+        };
+
+        // NOTE(unsafe) both regions only contain initialized elements
+        unsafe {
+            (
+                slice::from_raw_parts(older.as_ptr() as *const T, older.len()),
+                slice::from_raw_parts(newer.as_ptr() as *const T, newer.len()),
+            )
+        }
+    }
+
+    /// Returns the contents of the buffer as a pair of mutable slices, which when concatenated
+    /// hold the elements in chronological order, oldest first.
+    ///
+    /// See [`as_slices`](#method.as_slices).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4, 5, 6].iter());
+    ///
+    /// let (older, newer) = x.as_mut_slices();
+    /// older[0] = 0;
+    /// newer[1] = 9;
+    /// assert_eq!(x.as_slices(), (&[0, 4][..], &[5, 9][..]));
+    /// ```
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (older, newer) = if self.filled {
+            let (newer, older) = self.data.split_at_mut(self.write_at);
+            (older, newer)
+        } else {
+            (&mut self.data[..self.write_at], &mut [][..])
+        };
+
+        // NOTE(unsafe) both regions only contain initialized elements
+        unsafe {
+            (
+                slice::from_raw_parts_mut(older.as_mut_ptr() as *mut T, older.len()),
+                slice::from_raw_parts_mut(newer.as_mut_ptr() as *mut T, newer.len()),
+            )
+        }
+    }
+
+    /// Returns an iterator over the elements of the buffer in chronological order, oldest first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4, 5, 6].iter());
+    ///
+    /// let mut iter = x.oldest_ordered();
+    /// assert_eq!(iter.next(), Some(&3));
+    /// assert_eq!(iter.next(), Some(&4));
+    /// assert_eq!(iter.next(), Some(&5));
+    /// assert_eq!(iter.next(), Some(&6));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn oldest_ordered(&self) -> OldestOrdered<'_, T, N> {
+        OldestOrdered {
+            buf: self,
+            front: 0,
+            back: self.len(),
+        }
+    }
+
+    /// Returns an iterator over the elements of the buffer in reverse chronological order, most
+    /// recent first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4, 5, 6].iter());
+    ///
+    /// let mut iter = x.recent_ordered();
+    /// assert_eq!(iter.next(), Some(&6));
+    /// assert_eq!(iter.next(), Some(&5));
+    /// assert_eq!(iter.next(), Some(&4));
+    /// assert_eq!(iter.next(), Some(&3));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn recent_ordered(&self) -> iter::Rev<OldestOrdered<'_, T, N>> {
+        self.oldest_ordered().rev()
+    }
+
+    /// Returns an iterator over every `k`-th element of the buffer in chronological order,
+    /// starting with the oldest element.
+    ///
+    /// See [`decimate_into`](#method.decimate_into) to collect a fixed number of elements instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 8> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4, 5, 6, 7].iter());
+    ///
+    /// let mut iter = x.downsample(3);
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert_eq!(iter.next(), Some(&4));
+    /// assert_eq!(iter.next(), Some(&7));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn downsample(&self, k: usize) -> iter::StepBy<OldestOrdered<'_, T, N>> {
+        self.oldest_ordered().step_by(k)
+    }
+
+    /// Returns an iterator over the elements of the buffer in chronological order, oldest first.
+    ///
+    /// This is [`oldest_ordered`](#method.oldest_ordered), under the name used by the other
+    /// containers and by `for x in &buffer`.
+    pub fn iter(&self) -> OldestOrdered<'_, T, N> {
+        self.oldest_ordered()
+    }
+
+    /// Returns an iterator that allows modifying each element of the buffer, in chronological
+    /// order, oldest first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4, 5].iter());
+    ///
+    /// for (i, el) in x.iter_mut().enumerate() {
+    ///     *el += i as u8 * 10;
+    /// }
+    /// assert_eq!(x.as_slice(), [35, 2, 13, 24]);
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (older, newer) = self.as_mut_slices();
+        IterMut {
+            iter: older.iter_mut().chain(newer.iter_mut()),
+        }
+    }
+
+    /// Returns the number of slots of the backing array that are not initialized yet.
+    ///
+    /// This is `capacity() - len()`, and is 0 once the buffer is full. The initialized slots are
+    /// the ones returned by [`as_slice`](#method.as_slice).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2].iter());
+    /// assert_eq!(x.as_slice(), [1, 2]);
+    /// assert_eq!(x.uninit_count(), 2);
+    /// ```
+    #[inline]
+    pub const fn uninit_count(&self) -> usize {
+        N - self.len()
+    }
+
+    /// Returns the contents of the buffer in chronological order, oldest first.
+    ///
+    /// If the elements are already stored in chronological order, which is the case until the
+    /// buffer wraps around for the first time, they are borrowed. Otherwise they are cloned into a
+    /// [`Vec`](../type.Vec.html).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{histbuf::Ordered, HistoryBuffer};
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3].iter());
+    /// assert!(matches!(x.ordered(), Ordered::Borrowed(&[1, 2, 3])));
+    ///
+    /// x.extend([4, 5].iter());
+    /// assert!(matches!(x.ordered(), Ordered::Owned(_)));
+    /// assert_eq!(*x.ordered(), [2, 3, 4, 5]);
+    /// ```
+    pub fn ordered(&self) -> Ordered<'_, T, N>
+    where
+        T: Clone,
+    {
+        if !self.filled || self.write_at == 0 {
+            Ordered::Borrowed(self.as_slice())
+        } else {
+            let mut vec = Vec::new();
+            // NOTE(unsafe) the `Vec` has the same capacity as the buffer
+            unsafe {
+                for el in self.data[self.write_at..]
+                    .iter()
+                    .chain(&self.data[..self.write_at])
+                {
+                    vec.push_unchecked((*el.as_ptr()).clone());
+                }
+            }
+            Ordered::Owned(vec)
+        }
+    }
+
+    /// Returns the contents of the buffer in chronological order as a single slice, if they are
+    /// already stored in that order.
+    ///
+    /// This is the case until the buffer wraps around for the first time. Otherwise an error is
+    /// returned and [`make_contiguous`](#method.make_contiguous) can be used to reorder the
+    /// backing array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3].iter());
+    /// assert_eq!(x.try_as_ordered_slice(), Ok(&[1, 2, 3][..]));
+    ///
+    /// x.extend([4, 5].iter());
+    /// assert!(x.try_as_ordered_slice().is_err());
+    ///
+    /// x.make_contiguous();
+    /// assert_eq!(x.try_as_ordered_slice(), Ok(&[2, 3, 4, 5][..]));
+    /// ```
+    pub fn try_as_ordered_slice(&self) -> Result<&[T], WrappedError> {
+        if !self.filled || self.write_at == 0 {
+            Ok(self.as_slice())
+        } else {
+            Err(WrappedError)
+        }
+    }
+
+    /// Reorders the backing array so that the contents of the buffer are stored in chronological
+    /// order, and returns them as a mutable slice, oldest first.
+    ///
+    /// This doesn't change the contents of the buffer, only their order in the backing array. The
+    /// backing array is only rotated if the contents wrap around its end, so calling this again
+    /// before the next write that wraps around is cheap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4, 5, 6].iter());
+    /// assert_eq!(x.as_slice(), [5, 6, 3, 4]);
+    ///
+    /// assert_eq!(x.make_contiguous(), [3, 4, 5, 6]);
+    /// assert_eq!(x.as_slice(), [3, 4, 5, 6]);
+    /// assert_eq!(x.recent(), Some(&6));
+    /// ```
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        self.rotate_to_start();
+        unsafe { slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut _, self.len()) }
+    }
+
+    /// Cycles the values of the buffer by `k` positions in chronological order, keeping the fill
+    /// level.
+    ///
+    /// A positive `k` moves every value `k` positions towards the most recent end, and the values
+    /// that move past it wrap around to the oldest end; a negative `k` moves values towards the
+    /// oldest end. Unlike [`make_contiguous`](#method.make_contiguous), which only moves elements
+    /// within the backing array, this changes which value is at each chronological position, e.g.
+    /// what [`recent`](#method.recent) returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4].iter());
+    ///
+    /// x.circular_shift(1);
+    /// assert_eq!(x.recent(), Some(&3));
+    /// assert_eq!(&*x.ordered(), [4, 1, 2, 3]);
+    ///
+    /// x.circular_shift(-2);
+    /// assert_eq!(&*x.ordered(), [2, 3, 4, 1]);
+    /// ```
+    pub fn circular_shift(&mut self, k: isize) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+
+        let shift = k.rem_euclid(len as isize) as usize;
+        self.make_contiguous().rotate_right(shift);
+    }
+
+    /// Removes the elements for which `filter` returns `true` and yields them by value, oldest
+    /// first.
+    ///
+    /// The elements that are kept are compacted at the start of the buffer in chronological
+    /// order, so the buffer is no longer full if anything was removed.
+    ///
+    /// If the returned iterator is dropped before it is exhausted, the elements that were not yet
+    /// visited are kept in the buffer, whether or not they match `filter`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4, 5].iter());
+    ///
+    /// let mut evens = x.drain_filter(|x| x % 2 == 0);
+    /// assert_eq!(evens.next(), Some(2));
+    /// assert_eq!(evens.next(), Some(4));
+    /// assert_eq!(evens.next(), None);
+    /// drop(evens);
+    ///
+    /// assert_eq!(x.len(), 2);
+    /// assert_eq!(x.recent(), Some(&5));
+    /// ```
+    pub fn drain_filter<F>(&mut self, filter: F) -> DrainFilter<'_, T, F, N>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.rotate_to_start();
+        let len = self.len();
+
+        // Leak the elements rather than double drop them if the `DrainFilter` is forgotten; the
+        // fill level is restored when it's dropped
+        self.write_at = 0;
+        self.filled = false;
+
+        DrainFilter {
+            buf: self,
+            filter,
+            idx: 0,
+            del: 0,
+            len,
+        }
+    }
+
+    /// Empties the buffer, returning an iterator that yields the removed elements by value, oldest
+    /// first.
+    ///
+    /// The buffer is empty once the iterator is created; elements the iterator doesn't yield are
+    /// dropped along with it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{HistoryBuffer, String};
+    ///
+    /// let mut x: HistoryBuffer<String<8>, 2> = HistoryBuffer::new();
+    /// x.write("a".into());
+    /// x.write("b".into());
+    /// x.write("c".into());
+    ///
+    /// let mut drain = x.drain();
+    /// assert_eq!(drain.next().as_deref(), Some("b"));
+    /// assert_eq!(drain.next().as_deref(), Some("c"));
+    /// assert_eq!(drain.next(), None);
+    /// drop(drain);
+    ///
+    /// assert_eq!(x.len(), 0);
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, T, N> {
+        let len = self.len();
+        let oldest = self.ordered_index(0);
+
+        // Leak the elements rather than double drop them if the `Drain` is forgotten
+        self.write_at = 0;
+        self.filled = false;
+
+        Drain {
+            buf: self,
+            oldest,
+            front: 0,
+            back: len,
+        }
+    }
+
+    /// Retains only the elements for which `f` returns `true`, passing it the chronological index
+    /// of each element along with the element, where index 0 is the oldest element.
+    ///
+    /// The elements that are kept are compacted at the start of the buffer in chronological
+    /// order, like with [`drain_filter`](#method.drain_filter), and the others are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4, 5].iter());
+    ///
+    /// x.retain_indexed(|i, _| i % 2 == 0);
+    /// assert_eq!(x.as_slice(), [2, 4]);
+    /// ```
+    pub fn retain_indexed<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize, &T) -> bool,
+    {
+        let mut i = 0;
+        self.drain_filter(|el| {
+            let keep = f(i, el);
+            i += 1;
+            !keep
+        })
+        .for_each(drop);
+    }
+
+    /// Returns a copy of the buffer subsampled down to at most `M` elements, in chronological
+    /// order.
+    ///
+    /// If the buffer holds no more than `M` elements they are all copied. Otherwise the buffer is
+    /// split into `M` equally sized spans and the first element of each span is picked, i.e. the
+    /// element at chronological index `i * len / M` for `i` in `0..M`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 8> = HistoryBuffer::new();
+    /// x.extend([0, 1, 2, 3, 4, 5, 6, 7].iter());
+    ///
+    /// let y: HistoryBuffer<u8, 4> = x.decimate_into();
+    /// assert_eq!(y.as_slice(), [0, 2, 4, 6]);
+    /// ```
+    pub fn decimate_into<const M: usize>(&self) -> HistoryBuffer<T, M>
+    where
+        T: Copy,
+    {
+        let mut out = HistoryBuffer::new();
+        let len = self.len();
+        let n = if len < M { len } else { M };
+
+        for i in 0..n {
+            out.write(unsafe { *self.get_ordered_unchecked(i * len / n) });
+        }
+
+        out
+    }
+
+    /// Returns a copy of the buffer linearly resampled to exactly `M` elements, in chronological
+    /// order.
+    ///
+    /// Both endpoints are kept: the first output element is the oldest element and the last output
+    /// element is the most recent one, with the others interpolated at evenly spaced positions in
+    /// between. This works for both upsampling and downsampling. If `M` is 1 the output holds the
+    /// oldest element only, and if the buffer is empty so is the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<f64, 4> = HistoryBuffer::new();
+    /// x.extend([0.0, 2.0, 4.0].iter());
+    ///
+    /// let y: HistoryBuffer<f64, 5> = x.resample();
+    /// assert_eq!(y.as_slice(), [0.0, 1.0, 2.0, 3.0, 4.0]);
+    /// ```
+    pub fn resample<const M: usize>(&self) -> HistoryBuffer<T, M>
+    where
+        T: Into<f64> + From<f64> + Copy,
+    {
+        let mut out = HistoryBuffer::new();
+        let len = self.len();
+        if len == 0 {
+            return out;
+        }
+
+        for j in 0..M {
+            let pos = if M > 1 {
+                (j * (len - 1)) as f64 / (M - 1) as f64
+            } else {
+                0.
+            };
+            let i = pos as usize;
+            let a: f64 = unsafe { *self.get_ordered_unchecked(i) }.into();
+
+            let value = if i + 1 < len {
+                let b: f64 = unsafe { *self.get_ordered_unchecked(i + 1) }.into();
+                a + (b - a) * (pos - i as f64)
+            } else {
+                a
+            };
+            out.write(T::from(value));
+        }
+
+        out
+    }
+
+    /// Returns the Pearson correlation coefficient between the contents of two buffers, pairing
+    /// the elements by chronological index.
+    ///
+    /// Returns `None` if the buffers hold a different number of elements, or if the contents of
+    /// either buffer have zero variance (which includes empty buffers).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<i8, 4> = HistoryBuffer::new();
+    /// let mut y: HistoryBuffer<i8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4].iter());
+    /// y.extend([8, 6, 4, 2].iter());
+    ///
+    /// let r = x.correlation(&y).unwrap();
+    /// assert!((r + 1.0).abs() < 1e-12);
+    /// ```
+    pub fn correlation(&self, other: &HistoryBuffer<T, N>) -> Option<f64>
+    where
+        T: Into<f64> + Copy,
+    {
+        let len = self.len();
+        if len != other.len() || len == 0 {
+            return None;
+        }
+
+        let (mut mean_x, mut mean_y) = (0.0, 0.0);
+        for i in 0..len {
+            mean_x += unsafe { *self.get_ordered_unchecked(i) }.into();
+            mean_y += unsafe { *other.get_ordered_unchecked(i) }.into();
+        }
+        mean_x /= len as f64;
+        mean_y /= len as f64;
+
+        let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+        for i in 0..len {
+            let dx = unsafe { *self.get_ordered_unchecked(i) }.into() - mean_x;
+            let dy = unsafe { *other.get_ordered_unchecked(i) }.into() - mean_y;
+            cov += dx * dy;
+            var_x += dx * dx;
+            var_y += dy * dy;
+        }
+
+        if var_x == 0.0 || var_y == 0.0 {
+            None
+        } else {
+            // NOTE the square roots are taken separately so that `var_x * var_y` can't overflow
+            Some(cov / (sqrt(var_x) * sqrt(var_y)))
+        }
+    }
+
+    /// Returns an iterator over the chronological indices of the elements whose z-score exceeds
+    /// `z` in magnitude.
+    ///
+    /// The z-score of an element is its distance to the mean of the buffer in units of the
+    /// (population) standard deviation of the buffer, which are computed once up front. If all the
+    /// elements are equal there are no outliers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 8> = HistoryBuffer::new();
+    /// x.extend([5, 5, 6, 5, 40, 5, 4, 5].iter());
+    ///
+    /// let mut outliers = x.outliers(2.0);
+    /// assert_eq!(outliers.next(), Some(4));
+    /// assert_eq!(outliers.next(), None);
+    /// ```
+    pub fn outliers(&self, z: f64) -> impl Iterator<Item = usize> + '_
+    where
+        T: Into<f64> + Copy,
+    {
+        let len = self.len();
+
+        let mut mean = 0.0;
+        for i in 0..len {
+            mean += unsafe { *self.get_ordered_unchecked(i) }.into();
+        }
+        if len != 0 {
+            mean /= len as f64;
+        }
+
+        let mut var = 0.0;
+        for i in 0..len {
+            let d = unsafe { *self.get_ordered_unchecked(i) }.into() - mean;
+            var += d * d;
+        }
+        if len != 0 {
+            var /= len as f64;
+        }
+
+        let threshold = z * sqrt(var);
+        // a window with zero variance has no outliers
+        let n = if var == 0.0 { 0 } else { len };
+
+        (0..n).filter(move |&i| {
+            let d = unsafe { *self.get_ordered_unchecked(i) }.into() - mean;
+            d > threshold || d < -threshold
+        })
+    }
+
+    /// Returns a compact summary of the buffer, suitable for logging.
+    ///
+    /// The summary holds the fill level, the capacity and the most recent element instead of the
+    /// whole contents. See [`numeric_summary`](#method.numeric_summary) to also include the mean.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 8> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3].iter());
+    ///
+    /// let mut s: heapless::String<64> = heapless::String::new();
+    /// core::fmt::write(&mut s, format_args!("{}", x.summary())).unwrap();
+    /// assert_eq!(s, "len=3 capacity=8 recent=3");
+    /// ```
+    pub fn summary(&self) -> Summary<'_, T> {
+        Summary {
+            len: self.len(),
+            capacity: N,
+            recent: self.recent(),
+            mean: None,
+        }
+    }
+
+    /// Returns a compact summary of the buffer, including the mean of its elements.
+    ///
+    /// The mean has the type of the elements. For integers it is computed with integer arithmetic
+    /// only, summing in a wider type and rounding to the nearest integer with halves rounded up,
+    /// so that it's cheap on targets without a floating point unit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 8> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4].iter());
+    ///
+    /// let mut s: heapless::String<64> = heapless::String::new();
+    /// core::fmt::write(&mut s, format_args!("{}", x.numeric_summary())).unwrap();
+    /// assert_eq!(s, "len=4 capacity=8 recent=4 mean=3");
+    /// ```
+    pub fn numeric_summary(&self) -> Summary<'_, T>
+    where
+        T: Mean,
+    {
+        let mean = if self.len() == 0 {
+            None
+        } else {
+            Some(T::mean(self.as_slice()))
+        };
+
+        Summary {
+            mean,
+            ..self.summary()
+        }
+    }
+
+    /// Returns the mean of the elements rounded to the nearest integer, with halves rounded up, or
+    /// `None` if the buffer is empty.
+    ///
+    /// Unlike the floating point statistics, this only uses integer arithmetic, which is much
+    /// cheaper on targets without a floating point unit. The sum is accumulated in a `u64`, so it
+    /// can't overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u16, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 2, 2].iter());
+    /// assert_eq!(x.mean_u32(), Some(2));
+    ///
+    /// x.extend([3, 4].iter());
+    /// assert_eq!(x.mean_u32(), Some(3));
+    /// ```
+    pub fn mean_u32(&self) -> Option<u32>
+    where
+        T: Into<u32> + Copy,
+    {
+        let len = self.len() as u64;
+        if len == 0 {
+            return None;
+        }
+
+        let sum: u64 = self.as_slice().iter().map(|&x| u64::from(x.into())).sum();
+        Some(((sum + len / 2) / len) as u32)
+    }
+
+    /// Returns the sum of the absolute values of the elements, or `None` if the buffer is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<i8, 4> = HistoryBuffer::new();
+    /// x.extend([1, -2, 3].iter());
+    /// assert_eq!(x.sum_abs(), Some(6.0));
+    /// ```
+    pub fn sum_abs(&self) -> Option<f64>
+    where
+        T: Into<f64> + Copy,
+    {
+        if self.len() == 0 {
+            return None;
+        }
+
+        Some(
+            self.as_slice()
+                .iter()
+                .map(|&x| {
+                    let x = x.into();
+                    // NOTE `f64::abs` is not available in `core`
+                    if x < 0.0 {
+                        -x
+                    } else {
+                        x
+                    }
+                })
+                .sum(),
+        )
+    }
+
+    /// Returns the energy of the signal held by the buffer, i.e. the sum of the squares of the
+    /// elements, or `None` if the buffer is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<i8, 4> = HistoryBuffer::new();
+    /// x.extend([1, -2, 3].iter());
+    /// assert_eq!(x.energy(), Some(14.0));
+    /// ```
+    pub fn energy(&self) -> Option<f64>
+    where
+        T: Into<f64> + Copy,
+    {
+        if self.len() == 0 {
+            return None;
+        }
+
+        Some(
+            self.as_slice()
+                .iter()
+                .map(|&x| {
+                    let x = x.into();
+                    x * x
+                })
+                .sum(),
+        )
+    }
+
+    /// Returns an iterator over the gaps in a window of sequence numbers, oldest first.
+    ///
+    /// A gap is reported as the `(prev, next)` pair of chronologically adjacent elements where
+    /// `next` is not the successor of `prev`. Successors wrap around, so e.g. `255u8` followed by
+    /// `0u8` is not a gap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u16, 8> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 6, 7, 9].iter());
+    ///
+    /// let mut gaps = x.sequence_gaps();
+    /// assert_eq!(gaps.next(), Some((3, 6)));
+    /// assert_eq!(gaps.next(), Some((7, 9)));
+    /// assert_eq!(gaps.next(), None);
+    /// ```
+    pub fn sequence_gaps(&self) -> impl Iterator<Item = (T, T)> + '_
+    where
+        T: SequenceNumber,
+    {
+        (1..self.len()).filter_map(move |i| {
+            let prev = unsafe { *self.get_ordered_unchecked(i - 1) };
+            let next = unsafe { *self.get_ordered_unchecked(i) };
+            if next == prev.successor() {
+                None
+            } else {
+                Some((prev, next))
+            }
+        })
+    }
+
+    /// Returns an iterator that merges the contents of two buffers whose elements are sorted in
+    /// chronological order, yielding the elements of both in sorted order.
+    ///
+    /// The contents of each buffer must be sorted in ascending order, oldest first; otherwise the
+    /// order of the yielded elements is unspecified, but every element is still yielded exactly
+    /// once. Equal elements are yielded from `self` first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// let mut y: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 4, 6].iter());
+    /// y.extend([2, 3, 5, 7].iter());
+    ///
+    /// let mut merged = x.merge_sorted(&y);
+    /// for i in 1..8 {
+    ///     assert_eq!(merged.next(), Some(&i));
+    /// }
+    /// assert_eq!(merged.next(), None);
+    /// ```
+    pub fn merge_sorted<'a>(
+        &'a self,
+        other: &'a HistoryBuffer<T, N>,
+    ) -> impl Iterator<Item = &'a T> + 'a
+    where
+        T: Ord,
+    {
+        let (mut i, mut j) = (0, 0);
+        iter::from_fn(move || {
+            let x = if i < self.len() {
+                Some(unsafe { self.get_ordered_unchecked(i) })
+            } else {
+                None
+            };
+            let y = if j < other.len() {
+                Some(unsafe { other.get_ordered_unchecked(j) })
+            } else {
+                None
+            };
+
+            match (x, y) {
+                (Some(x), Some(y)) if y < x => {
+                    j += 1;
+                    Some(y)
+                }
+                (Some(x), _) => {
+                    i += 1;
+                    Some(x)
+                }
+                (None, y) => {
+                    j += 1;
+                    y
+                }
+            }
+        })
+    }
+
+    /// Returns the fraction of chronologically adjacent pairs of elements that cross zero, where
+    /// zero is `T::default()`.
+    ///
+    /// A pair crosses zero when one element is less than zero and the other isn't, i.e. zero
+    /// counts as positive. Returns `None` if the buffer holds fewer than two elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<i8, 5> = HistoryBuffer::new();
+    /// x.extend([1, -1, -2, 3, 4].iter());
+    ///
+    /// assert_eq!(x.zero_crossing_rate(), Some(0.5));
+    /// ```
+    pub fn zero_crossing_rate(&self) -> Option<f64>
+    where
+        T: PartialOrd + Default + Copy,
+    {
+        let len = self.len();
+        if len < 2 {
+            return None;
+        }
+
+        let zero = T::default();
+        let mut crossings = 0;
+        let mut below = unsafe { *self.get_ordered_unchecked(0) } < zero;
+        for i in 1..len {
+            let next_below = unsafe { *self.get_ordered_unchecked(i) } < zero;
+            if next_below != below {
+                crossings += 1;
+            }
+            below = next_below;
+        }
+
+        Some(crossings as f64 / (len - 1) as f64)
+    }
+
+    /// Returns an iterator over the differences between the elements of the buffer and those of
+    /// `baseline`, oldest first.
+    ///
+    /// The elements are paired by chronological index, starting from the oldest element of each
+    /// buffer. If the buffers hold a different number of elements, the iterator stops at the end of
+    /// the shorter one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut measured: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+    /// let mut predicted: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+    /// measured.extend([10, 12, 15].iter());
+    /// predicted.extend([10, 11, 16].iter());
+    ///
+    /// let mut residuals = measured.diff(&predicted);
+    /// assert_eq!(residuals.next(), Some(0));
+    /// assert_eq!(residuals.next(), Some(1));
+    /// assert_eq!(residuals.next(), Some(-1));
+    /// assert_eq!(residuals.next(), None);
+    /// ```
+    pub fn diff<'a>(&'a self, baseline: &'a HistoryBuffer<T, N>) -> impl Iterator<Item = T> + 'a
+    where
+        T: ops::Sub<Output = T> + Copy,
+    {
+        (0..cmp::min(self.len(), baseline.len())).map(move |i| unsafe {
+            *self.get_ordered_unchecked(i) - *baseline.get_ordered_unchecked(i)
+        })
+    }
+
+    /// Returns `true` if the `n` most recent elements of both buffers are equal, pairing them in
+    /// chronological order.
+    ///
+    /// The buffers may have different capacities. Returns `false` if either buffer holds fewer
+    /// than `n` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// let mut y: HistoryBuffer<u8, 8> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4].iter());
+    /// y.extend([9, 9, 3, 4].iter());
+    ///
+    /// assert!(x.recent_eq(&y, 2));
+    /// assert!(!x.recent_eq(&y, 3));
+    /// ```
+    pub fn recent_eq<const M: usize>(&self, other: &HistoryBuffer<T, M>, n: usize) -> bool
+    where
+        T: PartialEq,
+    {
+        let (len, other_len) = (self.len(), other.len());
+        if len < n || other_len < n {
+            return false;
+        }
+
+        (0..n).all(|i| unsafe {
+            self.get_ordered_unchecked(len - n + i)
+                == other.get_ordered_unchecked(other_len - n + i)
+        })
+    }
+
+    /// Returns an iterator over the pairs of chronologically adjacent elements, oldest first.
+    ///
+    /// Each item is a `(previous, next)` pair, so the buffer `[a, b, c]` yields `(a, b)` then
+    /// `(b, c)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+    /// x.extend([1, 4, 9].iter());
+    ///
+    /// let mut diffs = x.pairs().map(|(a, b)| b - a);
+    /// assert_eq!(diffs.next(), Some(3));
+    /// assert_eq!(diffs.next(), Some(5));
+    /// assert_eq!(diffs.next(), None);
+    /// ```
+    pub fn pairs(&self) -> impl DoubleEndedIterator<Item = (&T, &T)> + '_ {
+        (1..self.len()).map(move |i| unsafe {
+            (
+                self.get_ordered_unchecked(i - 1),
+                self.get_ordered_unchecked(i),
+            )
+        })
+    }
+
+    /// Returns an iterator over the pairs of chronologically adjacent elements, most recent
+    /// first.
+    ///
+    /// Each item is a `(next, previous)` pair, so the buffer `[a, b, c]` yields `(c, b)` then
+    /// `(b, a)`; this is the reverse of [`pairs`](#method.pairs) with each pair swapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+    /// x.extend([1, 4, 9].iter());
+    ///
+    /// let mut diffs = x.pairs_rev().map(|(a, b)| a - b);
+    /// assert_eq!(diffs.next(), Some(5));
+    /// assert_eq!(diffs.next(), Some(3));
+    /// assert_eq!(diffs.next(), None);
+    /// ```
+    pub fn pairs_rev(&self) -> impl DoubleEndedIterator<Item = (&T, &T)> + '_ {
+        self.pairs().rev().map(|(prev, next)| (next, prev))
+    }
+
+    /// Returns the number of times the value changes across the buffer, i.e. the number of
+    /// chronologically adjacent pairs of elements that differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<bool, 8> = HistoryBuffer::new();
+    /// x.extend([false, true, true, false, false, false].iter());
+    /// assert_eq!(x.transitions(), 2);
+    /// ```
+    pub fn transitions(&self) -> usize
+    where
+        T: PartialEq,
+    {
+        (1..self.len())
+            .filter(|&i| unsafe {
+                self.get_ordered_unchecked(i - 1) != self.get_ordered_unchecked(i)
+            })
+            .count()
+    }
+
+    /// Returns the number of most recent elements that are equal to the most recent element.
+    ///
+    /// This is the length of the run the buffer ends with: 0 if the buffer is empty, and at least
+    /// 1 otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<bool, 8> = HistoryBuffer::new();
+    /// x.extend([false, true, false, true, true].iter());
+    /// assert_eq!(x.recent_run_len(), 2);
+    /// ```
+    pub fn recent_run_len(&self) -> usize
+    where
+        T: PartialEq,
+    {
+        let len = self.len();
+        if len == 0 {
+            return 0;
+        }
+
+        let recent = unsafe { self.get_ordered_unchecked(len - 1) };
+        (0..len - 1)
+            .rev()
+            .take_while(|&i| unsafe { self.get_ordered_unchecked(i) } == recent)
+            .count()
+            + 1
+    }
+
+    /// Returns the run-length encoding of the contents of the buffer, in chronological order.
+    ///
+    /// Each item is a `(value, count)` pair for a run of `count` consecutive elements equal to
+    /// `value`. As there can't be more runs than elements, the result always fits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<char, 8> = HistoryBuffer::new();
+    /// x.extend(['a', 'a', 'b', 'c', 'c', 'c'].iter());
+    /// assert_eq!(x.run_length_encode(), [('a', 2), ('b', 1), ('c', 3)]);
+    /// ```
+    pub fn run_length_encode(&self) -> Vec<(T, usize), N>
+    where
+        T: PartialEq + Clone,
+    {
+        let mut runs: Vec<(T, usize), N> = Vec::new();
+
+        for i in 0..self.len() {
+            let el = unsafe { self.get_ordered_unchecked(i) };
+            match runs.last_mut() {
+                Some((value, count)) if value == el => *count += 1,
+                // NOTE(unsafe) there are never more runs than elements
+                _ => unsafe { runs.push_unchecked((el.clone(), 1)) },
+            }
+        }
+
+        runs
+    }
+
+    /// Multiplies the `K` most recent elements in place by the window coefficients `coeffs`.
+    ///
+    /// The coefficients are paired in chronological order: `coeffs[0]` multiplies the oldest of
+    /// the `K` elements and `coeffs[K - 1]` the most recent one. Older elements are left
+    /// untouched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer holds fewer than `K` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<f32, 4> = HistoryBuffer::new();
+    /// x.extend([2.0, 2.0, 2.0, 2.0].iter());
+    /// x.apply_window(&[0.5, 1.0, 0.5]);
+    /// assert_eq!(x.as_slice(), [2.0, 1.0, 2.0, 1.0]);
+    /// ```
+    pub fn apply_window<const K: usize>(&mut self, coeffs: &[T; K])
+    where
+        T: ops::Mul<Output = T> + Copy,
+    {
+        let len = self.len();
+        assert!(len >= K, "not enough elements for the window");
+
+        for (i, &coeff) in coeffs.iter().enumerate() {
+            let idx = self.ordered_index(len - K + i);
+            // NOTE(unsafe) `idx` is the position of an element, so it's initialized
+            let el = unsafe { &mut *self.data[idx].as_mut_ptr() };
+            *el = *el * coeff;
+        }
+    }
+
+    /// Calls `f` on every element of the buffer in chronological order, oldest first.
+    ///
+    /// This is a convenience for `oldest_ordered().for_each(f)`, e.g. for side effects like
+    /// feeding a checksum; it isn't meant to be faster.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4, 5].iter());
+    ///
+    /// let mut checksum = 0u8;
+    /// x.for_each(|&b| checksum = checksum.rotate_left(1) ^ b);
+    /// // the elements are visited in the order 2, 3, 4, 5
+    /// assert_eq!(checksum, ((2u8.rotate_left(1) ^ 3).rotate_left(1) ^ 4).rotate_left(1) ^ 5);
+    /// ```
+    pub fn for_each<F>(&self, mut f: F)
+    where
+        F: FnMut(&T),
+    {
+        let (older, newer) = self.as_slices();
+        for el in older.iter().chain(newer) {
+            f(el);
+        }
+    }
+
+    /// Swaps the contents of the buffer with the contents of `other`, in chronological order.
+    ///
+    /// After the swap the oldest element of the buffer is `other[0]`, and `other` holds the former
+    /// contents of the buffer from oldest to most recent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` doesn't have the same length as the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+    /// x.extend([1, 2, 3, 4].iter());
+    ///
+    /// let mut other = [7, 8, 9];
+    /// x.swap_with_slice(&mut other);
+    /// assert_eq!(other, [2, 3, 4]);
+    /// assert_eq!(x.recent(), Some(&9));
+    /// ```
+    pub fn swap_with_slice(&mut self, other: &mut [T]) {
+        assert_eq!(
+            self.len(),
+            other.len(),
+            "destination and source slices have different lengths"
+        );
+
+        for (i, el) in other.iter_mut().enumerate() {
+            let idx = self.ordered_index(i);
+            // NOTE(unsafe) `idx` is the position of an element, so it's initialized
+            mem::swap(unsafe { &mut *self.data[idx].as_mut_ptr() }, el);
+        }
+    }
+
+    /// Maps every element with `map` and folds the results with `reduce`, in chronological order
+    /// and in a single pass.
+    ///
+    /// This is the same as `iter.map(map).fold(init, reduce)` over the elements from oldest to
+    /// most recent; returns `init` if the buffer is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+    /// x.extend([1, -3, 2].iter());
+    ///
+    /// // largest squared value
+    /// let max = x.map_reduce(0, |&v| v * v, |a, b| a.max(b));
+    /// assert_eq!(max, 9);
+    /// ```
+    pub fn map_reduce<B, M, R>(&self, init: B, mut map: M, mut reduce: R) -> B
+    where
+        M: FnMut(&T) -> B,
+        R: FnMut(B, B) -> B,
+    {
+        let mut acc = init;
+        for i in 0..self.len() {
+            acc = reduce(acc, map(unsafe { self.get_ordered_unchecked(i) }));
+        }
+        acc
+    }
+
+    /// Returns the element at chronological index `i`, where index 0 is the oldest element.
+    ///
+    /// # Safety
+    ///
+    /// `i` must be less than `self.len()`.
+    unsafe fn get_ordered_unchecked(&self, i: usize) -> &T {
+        debug_assert!(i < self.len());
+
+        &*self.data.get_unchecked(self.ordered_index(i)).as_ptr()
+    }
+
+    /// Maps chronological index `i` to an index into the backing array.
+    fn ordered_index(&self, i: usize) -> usize {
+        let idx = if self.filled { self.write_at + i } else { i };
+        if idx >= N {
+            idx - N
+        } else {
+            idx
+        }
+    }
+
+    /// Rotates the backing array so that the oldest element is stored at index 0.
+    fn rotate_to_start(&mut self) {
+        if self.filled && self.write_at != 0 {
+            self.data.rotate_left(self.write_at);
+            self.write_at = 0;
+        }
+    }
+
+    /// Sets the fill level of a buffer whose `len` elements are stored, oldest first, at the
+    /// start of the backing array.
+    fn set_len_from_start(&mut self, len: usize) {
+        debug_assert!(len <= N);
+
+        if len < N {
+            self.write_at = len;
+            self.filled = false;
+        } else {
+            self.write_at = 0;
+            self.filled = true;
+        }
+    }
+}
+
+/// A sequence number, as used by [`HistoryBuffer::sequence_gaps`].
+///
+/// [`HistoryBuffer::sequence_gaps`]: struct.HistoryBuffer.html#method.sequence_gaps
+pub trait SequenceNumber: Copy + PartialEq {
+    /// Returns the next sequence number, wrapping around on overflow.
+    fn successor(self) -> Self;
+}
+
+macro_rules! impl_sequence_number {
+    ($($t:ty),*) => {
+        $(
+            impl SequenceNumber for $t {
+                #[inline]
+                fn successor(self) -> Self {
+                    self.wrapping_add(1)
+                }
+            }
+        )*
+    };
+}
+
+impl_sequence_number!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+// Integers are summed in a type twice as wide, which can't overflow, and the mean is rounded like
+// `mean_u32`
+macro_rules! impl_mean {
+    ($($t:ty => $acc:ty),*) => {
+        $(
+            impl Mean for $t {
+                #[inline]
+                fn mean(xs: &[Self]) -> Self {
+                    let len = xs.len() as $acc;
+                    let sum: $acc = xs.iter().map(|&x| x as $acc).sum();
+                    (sum + len / 2).div_euclid(len) as Self
+                }
+            }
+        )*
+    };
+}
+
+impl_mean!(
+    u8 => u64, u16 => u64, u32 => u64, u64 => u128, usize => u128,
+    i8 => i64, i16 => i64, i32 => i64, i64 => i128, isize => i128
+);
+
+impl Mean for f32 {
+    #[inline]
+    fn mean(xs: &[Self]) -> Self {
+        let sum: f64 = xs.iter().map(|&x| f64::from(x)).sum();
+        (sum / xs.len() as f64) as f32
+    }
+}
+
+impl Mean for f64 {
+    #[inline]
+    fn mean(xs: &[Self]) -> Self {
+        xs.iter().sum::<f64>() / xs.len() as f64
+    }
+}
+
+/// A compact summary of a [`HistoryBuffer`], suitable for logging.
+///
+/// This `struct` is created by the [`summary`] and [`numeric_summary`] methods on
+/// [`HistoryBuffer`]. It implements `Display`, and `defmt::Format` when the `defmt-impl` feature is
+/// enabled.
+///
+/// [`summary`]: struct.HistoryBuffer.html#method.summary
+/// [`numeric_summary`]: struct.HistoryBuffer.html#method.numeric_summary
+/// [`HistoryBuffer`]: struct.HistoryBuffer.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Summary<'a, T> {
+    /// The fill level of the buffer
+    pub len: usize,
+    /// The capacity of the buffer
+    pub capacity: usize,
+    /// The most recently written element
+    pub recent: Option<&'a T>,
+    /// The mean of the elements, if requested and the buffer is not empty
+    pub mean: Option<T>,
+}
+
+impl<T> fmt::Display for Summary<'_, T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "len={} capacity={}", self.len, self.capacity)?;
+        if let Some(recent) = self.recent {
+            write!(f, " recent={:?}", recent)?;
+        }
+        if let Some(mean) = &self.mean {
+            write!(f, " mean={:?}", mean)?;
+        }
+        Ok(())
+    }
+}
+
+/// The contents of a [`HistoryBuffer`] in chronological order, either borrowed or copied.
+///
+/// This `enum` is created by the [`ordered`] method on [`HistoryBuffer`]. It dereferences to a
+/// slice.
+///
+/// [`ordered`]: struct.HistoryBuffer.html#method.ordered
+/// [`HistoryBuffer`]: struct.HistoryBuffer.html
+#[derive(Debug)]
+pub enum Ordered<'a, T, const N: usize> {
+    /// The buffer's storage, which was already in chronological order
+    Borrowed(&'a [T]),
+    /// A chronologically ordered copy of the buffer's contents
+    Owned(Vec<T, N>),
+}
+
+impl<T, const N: usize> ops::Deref for Ordered<'_, T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match self {
+            Ordered::Borrowed(slice) => slice,
+            Ordered::Owned(vec) => vec,
+        }
+    }
+}
+
+/// The error returned by [`try_as_ordered_slice`] when the contents of a [`HistoryBuffer`] wrap
+/// around the end of its backing array.
+///
+/// [`try_as_ordered_slice`]: struct.HistoryBuffer.html#method.try_as_ordered_slice
+/// [`HistoryBuffer`]: struct.HistoryBuffer.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WrappedError;
+
+impl fmt::Display for WrappedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the buffer wraps around the end of its backing array")
+    }
+}
+
+/// Square root of a non-negative number, as `core` doesn't provide `f64::sqrt`.
+fn sqrt(x: f64) -> f64 {
+    if x <= 0.0 || x.is_nan() || x.is_infinite() {
+        return x;
+    }
+
+    // halving the exponent gives an estimate within a few percent, which Newton's method then
+    // refines to full precision
+    let mut y = f64::from_bits((x.to_bits() >> 1) + 0x1ff8_0000_0000_0000);
+    for _ in 0..6 {
+        y = 0.5 * (y + x / y);
+    }
+    y
+}
+
+/// Creates a [`HistoryBuffer`](histbuf/struct.HistoryBuffer.html) containing the arguments.
+///
+/// `history_buffer!` allows a `HistoryBuffer` to be defined with the same syntax as array
+/// expressions. There are two forms of this macro:
+///
+/// - Create a full buffer of `N` clones of an element, where `N` must be a constant:
+///
+/// ```
+/// use heapless::history_buffer;
+///
+/// let x = history_buffer![1u8; 3];
+/// assert_eq!(x.capacity(), 3);
+/// assert_eq!(x.as_slice(), [1, 1, 1]);
+/// ```
+///
+/// - Create a full buffer from a list of elements, oldest first, whose capacity is the number of
+///   elements:
+///
+/// ```
+/// use heapless::history_buffer;
+///
+/// let x = history_buffer![1, 2, 3];
+/// assert_eq!(x.capacity(), 3);
+/// assert_eq!(x.recent(), Some(&3));
+/// ```
+#[macro_export]
+macro_rules! history_buffer {
+    ($elem:expr; $n:expr) => {{
+        let mut buf = $crate::HistoryBuffer::<_, { $n }>::new();
+        let elem = $elem;
+        for _ in 0..buf.capacity() {
+            buf.write(::core::clone::Clone::clone(&elem));
+        }
+        buf
+    }};
+    ($($x:expr),+ $(,)?) => {{
+        let mut buf = $crate::HistoryBuffer::<
+            _,
+            { <[()]>::len(&[$($crate::__history_buffer_unit!($x)),+]) },
+        >::new();
+        $(buf.write($x);)+
+        buf
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __history_buffer_unit {
+    ($x:expr) => {
+        ()
+    };
+}
+
+/// Asserts at compile time that a [`HistoryBuffer`](histbuf/struct.HistoryBuffer.html) can hold
+/// at least a given number of elements.
+///
+/// `assert_fits!(HistoryBuffer<T, N>, needed)` fails to compile if `N` is less than `needed`.
+/// `N` and `needed` must be constants or const generic parameters. In generic code the check
+/// happens when the enclosing function is instantiated with a concrete `N`.
+///
+/// ```
+/// use heapless::{assert_fits, HistoryBuffer};
+///
+/// fn filter<const N: usize>(x: &HistoryBuffer<i32, N>) -> i32 {
+///     // this filter needs a window of at least 3 samples
+///     assert_fits!(HistoryBuffer<i32, N>, 3);
+///     x.as_slice().iter().sum()
+/// }
+///
+/// let x: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+/// filter(&x);
+/// ```
+///
+/// A buffer that is too small is rejected:
+///
+/// ```compile_fail
+/// use heapless::{assert_fits, HistoryBuffer};
+///
+/// fn filter<const N: usize>(x: &HistoryBuffer<i32, N>) -> i32 {
+///     assert_fits!(HistoryBuffer<i32, N>, 3);
+///     x.as_slice().iter().sum()
+/// }
+///
+/// let x: HistoryBuffer<i32, 2> = HistoryBuffer::new();
+/// filter(&x);
+/// ```
+#[macro_export]
+macro_rules! assert_fits {
+    (HistoryBuffer<$t:ty, $n:tt>, $needed:tt) => {{
+        let _: () = $crate::histbuf::__AssertFits::<{ $n }, { $needed }>::OK;
+    }};
+}
+
+#[doc(hidden)]
+pub struct __AssertFits<const N: usize, const NEEDED: usize>;
+
+impl<const N: usize, const NEEDED: usize> __AssertFits<N, NEEDED> {
+    // NOTE indexing out of bounds is a compile time error when this constant is evaluated
+    pub const OK: () = [()][(N < NEEDED) as usize];
+}
+
+/// A [`HistoryBuffer`] that keeps a running sum of its elements.
+///
+/// The sum is updated on every write by adding the new element and subtracting the evicted one,
+/// so [`sum`] and [`mean`] take constant time instead of scanning the buffer. The buffer can be
+/// read through `Deref`.
+///
+/// The sum must fit in `T`. With floating point elements, rounding errors accumulate over many
+/// writes; [`resync`] recomputes the sum from scratch.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::histbuf::RollingSum;
+///
+/// let mut x: RollingSum<i32, 4> = RollingSum::new();
+/// x.extend([1, 2, 3, 4, 5, 6].iter().copied());
+///
+/// assert_eq!(x.sum(), 3 + 4 + 5 + 6);
+/// assert_eq!(x.mean(), Some(4.5));
+/// assert_eq!(x.recent(), Some(&6));
+/// ```
+///
+/// [`HistoryBuffer`]: struct.HistoryBuffer.html
+/// [`sum`]: struct.RollingSum.html#method.sum
+/// [`mean`]: struct.RollingSum.html#method.mean
+/// [`resync`]: struct.RollingSum.html#method.resync
+pub struct RollingSum<T, const N: usize> {
+    buf: HistoryBuffer<T, N>,
+    sum: T,
+}
+
+impl<T, const N: usize> RollingSum<T, N>
+where
+    T: ops::Add<Output = T> + ops::Sub<Output = T> + Default + Copy,
+{
+    /// Constructs a new, empty buffer with a sum of zero.
+    pub fn new() -> Self {
+        Self {
+            buf: HistoryBuffer::new(),
+            sum: T::default(),
+        }
+    }
+
+    /// Writes an element to the buffer, overwriting the oldest value, and updates the sum.
+    pub fn write(&mut self, t: T) {
+        self.sum = self.sum + t;
+        if let Some(old) = self.buf.write_evict(t) {
+            self.sum = self.sum - old;
+        }
+    }
+
+    /// Clears the buffer and resets the sum to zero.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.sum = T::default();
+    }
+
+    /// Returns the sum of the elements of the buffer.
+    pub fn sum(&self) -> T {
+        self.sum
+    }
+
+    /// Returns the mean of the elements of the buffer, or `None` if the buffer is empty.
+    pub fn mean(&self) -> Option<f64>
+    where
+        T: Into<f64>,
+    {
+        match self.buf.len() {
+            0 => None,
+            len => Some(self.sum.into() / len as f64),
+        }
+    }
+
+    /// Recomputes the sum from the contents of the buffer, discarding any accumulated rounding
+    /// error.
+    pub fn resync(&mut self) {
+        self.sum = self
+            .buf
+            .oldest_ordered()
+            .fold(T::default(), |acc, &el| acc + el);
+    }
+
+    /// Returns the underlying buffer.
+    pub fn into_inner(self) -> HistoryBuffer<T, N> {
+        self.buf
+    }
+}
+
+impl<T, const N: usize> Default for RollingSum<T, N>
+where
+    T: ops::Add<Output = T> + ops::Sub<Output = T> + Default + Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> ops::Deref for RollingSum<T, N> {
+    type Target = HistoryBuffer<T, N>;
+
+    fn deref(&self) -> &HistoryBuffer<T, N> {
+        &self.buf
+    }
+}
+
+impl<T, const N: usize> Extend<T> for RollingSum<T, N>
+where
+    T: ops::Add<Output = T> + ops::Sub<Output = T> + Default + Copy,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iter {
+            self.write(item);
+        }
+    }
+}
+
+/// A [`HistoryBuffer`] that records a timestamp with every element.
+///
+/// The timestamps are provided by the caller, in any type that can be compared, e.g. ticks of a
+/// monotonic timer, and must not decrease from one write to the next. Each element is stored
+/// as a `(timestamp, value)` pair, and the underlying buffer can be read through `Deref`.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::histbuf::TimestampedHistoryBuffer;
+///
+/// let mut x: TimestampedHistoryBuffer<u32, i16, 8> = TimestampedHistoryBuffer::new();
+/// x.write(100, 3);
+/// x.write(150, 4);
+/// x.write(210, 5);
+///
+/// // the samples younger than 100 ticks at time 240
+/// let mut recent = x.since(240 - 100);
+/// assert_eq!(recent.next(), Some(&(150, 4)));
+/// assert_eq!(recent.next(), Some(&(210, 5)));
+/// assert_eq!(recent.next(), None);
+/// ```
+///
+/// [`HistoryBuffer`]: struct.HistoryBuffer.html
+pub struct TimestampedHistoryBuffer<I, T, const N: usize> {
+    buf: HistoryBuffer<(I, T), N>,
+}
+
+impl<I, T, const N: usize> TimestampedHistoryBuffer<I, T, N> {
+    /// Constructs a new, empty buffer.
+    pub const fn new() -> Self {
+        Self {
+            buf: HistoryBuffer::new(),
+        }
+    }
+
+    /// Writes an element recorded at time `timestamp` to the buffer, overwriting the oldest
+    /// element.
+    ///
+    /// `timestamp` must not be less than the timestamp of the most recent element.
+    pub fn write(&mut self, timestamp: I, value: T)
+    where
+        I: PartialOrd,
+    {
+        if let Some((last, _)) = self.buf.recent() {
+            debug_assert!(*last <= timestamp);
+        }
+
+        self.buf.write((timestamp, value));
+    }
+
+    /// Clears the buffer.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+
+    /// Returns an iterator over the elements recorded at or after time `timestamp`, in
+    /// chronological order.
+    pub fn since(&self, timestamp: I) -> impl Iterator<Item = &(I, T)> + '_
+    where
+        I: PartialOrd,
+    {
+        self.buf
+            .oldest_ordered()
+            .skip_while(move |(t, _)| *t < timestamp)
+    }
+
+    /// Returns the underlying buffer.
+    pub fn into_inner(self) -> HistoryBuffer<(I, T), N> {
+        self.buf
+    }
+}
+
+impl<I, T, const N: usize> Default for TimestampedHistoryBuffer<I, T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, T, const N: usize> ops::Deref for TimestampedHistoryBuffer<I, T, N> {
+    type Target = HistoryBuffer<(I, T), N>;
+
+    fn deref(&self) -> &HistoryBuffer<(I, T), N> {
+        &self.buf
+    }
+}
+
+/// A [`HistoryBuffer`] that passes every element evicted by a write to a hook, instead of
+/// dropping it.
+///
+/// The hook is kept across `clear`s, and is only invoked for elements that are overwritten by a
+/// write; elements removed in any other way, e.g. by `clear` or when the buffer is dropped, are
+/// dropped as usual. The buffer can be read through `Deref`.
+///
+/// # Examples
+///
+/// ```
+/// use core::sync::atomic::{AtomicU32, Ordering};
+/// use heapless::histbuf::HookedHistoryBuffer;
+///
+/// static EVICTED: AtomicU32 = AtomicU32::new(0);
+///
+/// let mut x: HookedHistoryBuffer<u32, 2> =
+///     HookedHistoryBuffer::new(|x| { EVICTED.fetch_add(x, Ordering::Relaxed); });
+/// x.extend([1, 2, 3, 4].iter());
+///
+/// assert_eq!(EVICTED.load(Ordering::Relaxed), 1 + 2);
+/// assert_eq!(x.recent(), Some(&4));
+/// ```
+///
+/// [`HistoryBuffer`]: struct.HistoryBuffer.html
+pub struct HookedHistoryBuffer<T, const N: usize> {
+    buf: HistoryBuffer<T, N>,
+    hook: fn(T),
+}
+
+impl<T, const N: usize> HookedHistoryBuffer<T, N> {
+    /// Constructs a new, empty buffer that passes every evicted element to `hook`.
+    pub const fn new(hook: fn(T)) -> Self {
+        Self {
+            buf: HistoryBuffer::new(),
+            hook,
+        }
+    }
+
+    /// Writes an element to the buffer, overwriting the oldest value, which is passed to the
+    /// hook.
+    pub fn write(&mut self, t: T) {
+        // NOTE the hook is only called once the buffer is consistent again, so that a panic in it
+        // can't lead to a double drop
+        self.buf.write_with(t, self.hook);
+    }
+
+    /// Clones and writes all elements in a slice to the buffer.
+    ///
+    /// The hook sees every overwritten element, even if the slice is longer than the buffer.
+    pub fn extend_from_slice(&mut self, other: &[T])
+    where
+        T: Clone,
+    {
+        for item in other {
+            self.write(item.clone());
+        }
+    }
+
+    /// Clears the buffer, dropping its elements without passing them to the hook.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+
+    /// Returns the underlying buffer and the hook.
+    pub fn into_parts(self) -> (HistoryBuffer<T, N>, fn(T)) {
+        (self.buf, self.hook)
+    }
+}
+
+impl<T, const N: usize> ops::Deref for HookedHistoryBuffer<T, N> {
+    type Target = HistoryBuffer<T, N>;
+
+    fn deref(&self) -> &HistoryBuffer<T, N> {
+        &self.buf
+    }
+}
+
+impl<T, const N: usize> Extend<T> for HookedHistoryBuffer<T, N> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iter {
+            self.write(item);
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Extend<&'a T> for HookedHistoryBuffer<T, N>
+where
+    T: 'a + Clone,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
+/// An iterator over the elements of a [`HistoryBuffer`] in chronological order.
+///
+/// This `struct` is created by the [`oldest_ordered`] and [`recent_ordered`] methods on
+/// [`HistoryBuffer`]. See their documentation for more.
+///
+/// [`oldest_ordered`]: struct.HistoryBuffer.html#method.oldest_ordered
+/// [`recent_ordered`]: struct.HistoryBuffer.html#method.recent_ordered
+/// [`HistoryBuffer`]: struct.HistoryBuffer.html
+pub struct OldestOrdered<'a, T, const N: usize> {
+    buf: &'a HistoryBuffer<T, N>,
+    // chronological index of the next element yielded from the front
+    front: usize,
+    // chronological index after the next element yielded from the back
+    back: usize,
+}
+
+impl<T, const N: usize> Clone for OldestOrdered<'_, T, N> {
+    fn clone(&self) -> Self {
+        Self {
+            buf: self.buf,
+            front: self.front,
+            back: self.back,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for OldestOrdered<'a, T, N> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
+        }
+
+        let el = unsafe { self.buf.get_ordered_unchecked(self.front) };
+        self.front += 1;
+        Some(el)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for OldestOrdered<'_, T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(unsafe { self.buf.get_ordered_unchecked(self.back) })
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for OldestOrdered<'_, T, N> {}
+
+impl<T, const N: usize> iter::FusedIterator for OldestOrdered<'_, T, N> {}
+
+/// A mutable iterator over the elements of a [`HistoryBuffer`] in chronological order.
+///
+/// This `struct` is created by the [`iter_mut`] method on [`HistoryBuffer`]. See its
+/// documentation for more.
+///
+/// [`iter_mut`]: struct.HistoryBuffer.html#method.iter_mut
+/// [`HistoryBuffer`]: struct.HistoryBuffer.html
+pub struct IterMut<'a, T> {
+    iter: iter::Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> iter::FusedIterator for IterMut<'_, T> {}
+
+/// An iterator that moves the elements out of a [`HistoryBuffer`] in chronological order.
+///
+/// This `struct` is created by the `into_iter` method on [`HistoryBuffer`] (provided by the
+/// `IntoIterator` trait).
+///
+/// [`HistoryBuffer`]: struct.HistoryBuffer.html
+pub struct IntoIter<T, const N: usize> {
+    buf: HistoryBuffer<T, N>,
+    // chronological index of the next element yielded from the front
+    front: usize,
+    // chronological index after the next element yielded from the back
+    back: usize,
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+
+        let el = unsafe { ptr::read(self.buf.get_ordered_unchecked(self.front)) };
+        self.front += 1;
+        Some(el)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(unsafe { ptr::read(self.buf.get_ordered_unchecked(self.back)) })
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> iter::FusedIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> Drop for IntoIter<T, N> {
+    fn drop(&mut self) {
+        let oldest = self.buf.ordered_index(0);
+
+        // NOTE empty the buffer first so that a panicking destructor leaks the remaining elements
+        // instead of dropping them twice
+        self.buf.write_at = 0;
+        self.buf.filled = false;
+
+        for i in self.front..self.back {
+            let idx = if oldest + i >= N {
+                oldest + i - N
+            } else {
+                oldest + i
+            };
+            unsafe { ptr::drop_in_place(self.buf.data[idx].as_mut_ptr()) }
+        }
+    }
+}
+
+/// A draining iterator over the elements of a [`HistoryBuffer`], in chronological order.
+///
+/// This `struct` is created by the [`drain`] method on [`HistoryBuffer`]. See its documentation
+/// for more.
+///
+/// [`drain`]: struct.HistoryBuffer.html#method.drain
+/// [`HistoryBuffer`]: struct.HistoryBuffer.html
+pub struct Drain<'a, T, const N: usize> {
+    buf: &'a mut HistoryBuffer<T, N>,
+    // index of the oldest element in the backing array
+    oldest: usize,
+    // chronological index of the next element yielded from the front
+    front: usize,
+    // chronological index after the next element yielded from the back
+    back: usize,
+}
+
+impl<T, const N: usize> Drain<'_, T, N> {
+    /// Moves out the element at chronological index `i`.
+    ///
+    /// # Safety
+    ///
+    /// The element must not have been moved out already.
+    unsafe fn take(&mut self, i: usize) -> T {
+        let idx = if self.oldest + i >= N {
+            self.oldest + i - N
+        } else {
+            self.oldest + i
+        };
+        self.buf.data[idx].as_ptr().read()
+    }
+}
+
+impl<T, const N: usize> Iterator for Drain<'_, T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.front += 1;
+        Some(unsafe { self.take(self.front - 1) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for Drain<'_, T, N> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(unsafe { self.take(self.back) })
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for Drain<'_, T, N> {}
+
+impl<T, const N: usize> iter::FusedIterator for Drain<'_, T, N> {}
+
+impl<T, const N: usize> Drop for Drain<'_, T, N> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+/// An iterator that removes the elements of a [`HistoryBuffer`] that match a predicate.
+///
+/// This `struct` is created by the [`drain_filter`] method on [`HistoryBuffer`]. See its
+/// documentation for more.
+///
+/// [`drain_filter`]: struct.HistoryBuffer.html#method.drain_filter
+/// [`HistoryBuffer`]: struct.HistoryBuffer.html
+pub struct DrainFilter<'a, T, F, const N: usize>
+where
+    F: FnMut(&T) -> bool,
+{
+    buf: &'a mut HistoryBuffer<T, N>,
+    filter: F,
+    // index of the next element to visit
+    idx: usize,
+    // number of elements removed so far
+    del: usize,
+    // number of elements in the buffer when the drain started
+    len: usize,
+}
+
+impl<T, F, const N: usize> Iterator for DrainFilter<'_, T, F, N>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let data = self.buf.data.as_mut_ptr() as *mut T;
+
+        while self.idx < self.len {
+            let i = self.idx;
+            let remove = (self.filter)(unsafe { &*data.add(i) });
+            // NOTE only advance once `filter` has returned, so that on a panic the element is
+            // kept by `Drop`
+            self.idx += 1;
+
+            if remove {
+                self.del += 1;
+                return Some(unsafe { data.add(i).read() });
+            } else if self.del > 0 {
+                unsafe { ptr::copy_nonoverlapping(data.add(i), data.add(i - self.del), 1) }
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.len - self.idx))
+    }
+}
+
+impl<T, F, const N: usize> Drop for DrainFilter<'_, T, F, N>
+where
+    F: FnMut(&T) -> bool,
+{
+    fn drop(&mut self) {
+        // keep the elements that were not visited, shifting them over the holes
+        if self.del > 0 && self.idx < self.len {
+            unsafe {
+                let data = self.buf.data.as_mut_ptr() as *mut T;
+                ptr::copy(
+                    data.add(self.idx),
+                    data.add(self.idx - self.del),
+                    self.len - self.idx,
+                );
+            }
+        }
+
+        self.buf.set_len_from_start(self.len - self.del);
+    }
+}
+
+impl<const N: usize> HistoryBuffer<f32, N> {
+    /// Converts the contents of the buffer to Q15 fixed-point numbers, writing them into `dst`.
+    ///
+    /// `dst` is cleared first, then the converted elements are written in chronological order, so
+    /// that the two buffers hold the same samples in the same order. Values are rounded to the
+    /// nearest Q15 number and clamped to the `[-1.0, 1.0)` range it can represent; `NaN` is
+    /// converted to 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::HistoryBuffer;
+    ///
+    /// let mut x: HistoryBuffer<f32, 4> = HistoryBuffer::new();
+    /// x.extend([0.5, -0.25, 1.0].iter());
+    ///
+    /// let mut y = HistoryBuffer::new();
+    /// x.to_q15(&mut y);
+    /// assert_eq!(y.as_slice(), [16384, -8192, 32767]);
+    /// ```
+    pub fn to_q15(&self, dst: &mut HistoryBuffer<i16, N>) {
+        dst.clear();
+
+        for i in 0..self.len() {
+            let x = unsafe { *self.get_ordered_unchecked(i) } * 32768.0;
+            // NOTE(as) float to integer casts saturate, and `round` is not available in `core`
+            let q = (x + if x < 0.0 { -0.5 } else { 0.5 }) as i32;
+            dst.write(if q > i16::MAX as i32 {
+                i16::MAX
+            } else if q < i16::MIN as i32 {
+                i16::MIN
+            } else {
+                q as i16
+            });
+        }
+    }
+}
+
+#[cfg(feature = "embedded-io-impl")]
+impl<const N: usize> HistoryBuffer<u8, N> {
+    /// Reads up to `max` bytes from `reader` into the buffer, overwriting the oldest bytes, and
+    /// returns the number of bytes read.
+    ///
+    /// The bytes are read directly into the backing array. Short reads are retried until `max`
+    /// bytes have been read or `reader` reaches EOF, i.e. returns `Ok(0)`; if more than `N` bytes
+    /// are read, only the last `N` are kept.
+    ///
+    /// If `reader` returns an error, it is returned and the bytes read by earlier calls are kept,
+    /// but the oldest bytes of a full buffer may have been overwritten with unspecified values.
+    ///
+    /// This method is only available with the `embedded-io-impl` feature.
+    pub fn fill_from_read<R>(&mut self, reader: &mut R, max: usize) -> Result<usize, R::Error>
+    where
+        R: embedded_io::Read + ?Sized,
+    {
+        let mut read = 0;
+
+        while read < max && N != 0 {
+            let end = self.write_at + cmp::min(N - self.write_at, max - read);
+            let chunk = &mut self.data[self.write_at..end];
+            if !self.filled {
+                for slot in chunk.iter_mut() {
+                    *slot = MaybeUninit::new(0);
+                }
+            }
+            // NOTE(unsafe) every slot of `chunk` is initialized at this point
+            let buf =
+                unsafe { slice::from_raw_parts_mut(chunk.as_mut_ptr() as *mut u8, chunk.len()) };
+
+            let n = reader.read(buf)?;
+            if n == 0 {
+                break;
+            }
+            debug_assert!(n <= end - self.write_at);
+
+            read += n;
+            self.write_at += n;
+            if self.write_at == N {
+                self.write_at = 0;
+                self.filled = true;
+            }
+        }
+
+        Ok(read)
+    }
+}
+
+impl<T, const N: usize> Extend<T> for HistoryBuffer<T, N> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iter.into_iter() {
+            self.write(item);
+        }
+    }
+}
+
+impl<'a, T, const N: usize> Extend<&'a T> for HistoryBuffer<T, N>
+where
+    T: 'a + Clone,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for HistoryBuffer<T, N> {
+    /// Creates a full buffer from an array, whose first element is the oldest one.
+    fn from(array: [T; N]) -> Self {
+        let array = ManuallyDrop::new(array);
+        Self {
+            // NOTE(unsafe) `MaybeUninit<T>` has the same layout as `T`, and the elements are moved
+            data: unsafe { ptr::read(&*array as *const [T; N] as *const [MaybeUninit<T>; N]) },
+            write_at: 0,
+            filled: true,
+        }
+    }
+}
+
+impl<T, const N: usize> Clone for HistoryBuffer<T, N>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut out = Self::new();
+
+        if self.filled {
+            for (dst, src) in out.data.iter_mut().zip(self.data.iter()) {
+                *dst = MaybeUninit::new(unsafe { &*src.as_ptr() }.clone());
+            }
+            // NOTE only mark the elements as initialized once all of them are, so that a panic in
+            // `clone` leaks the clones instead of dropping uninitialized slots
+            out.write_at = self.write_at;
+            out.filled = true;
+        } else {
+            for src in &self.data[..self.write_at] {
+                out.data[out.write_at] = MaybeUninit::new(unsafe { &*src.as_ptr() }.clone());
+                out.write_at += 1;
+            }
+        }
+
+        out
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for HistoryBuffer<T, N>
+where
+    T: fmt::Debug,
+{
+    /// Formats the elements of the buffer as a list, in chronological order.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.oldest_ordered()).finish()
+    }
+}
+
+impl<A, B, const N1: usize, const N2: usize> PartialEq<HistoryBuffer<B, N2>>
+    for HistoryBuffer<A, N1>
+where
+    A: PartialEq<B>,
+{
+    /// Compares the elements of the buffers in chronological order, regardless of where they are
+    /// stored in the backing arrays.
+    fn eq(&self, other: &HistoryBuffer<B, N2>) -> bool {
+        self.len() == other.len()
+            && self
+                .oldest_ordered()
+                .zip(other.oldest_ordered())
+                .all(|(a, b)| a == b)
+    }
+}
+
+impl<T, const N: usize> Eq for HistoryBuffer<T, N> where T: Eq {}
+
+impl<T, const N: usize> ops::Index<usize> for HistoryBuffer<T, N> {
+    type Output = T;
+
+    /// Returns the element written `age` writes before the most recent one; see
+    /// [`get`](struct.HistoryBuffer.html#method.get).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `age` is not less than `len()`.
+    fn index(&self, age: usize) -> &T {
+        self.get(age).expect("index out of bounds")
+    }
+}
+
+impl<T, const N: usize> IntoIterator for HistoryBuffer<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let back = self.len();
+        IntoIter {
+            buf: self,
+            front: 0,
+            back,
+        }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a HistoryBuffer<T, N> {
+    type Item = &'a T;
+    type IntoIter = OldestOrdered<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut HistoryBuffer<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, const N: usize> Drop for HistoryBuffer<T, N> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                self.data.as_mut_ptr() as *mut T,
                 self.len(),
             ))
         }
     }
-}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HookedHistoryBuffer, RollingSum, TimestampedHistoryBuffer, WrappedError};
+    use crate::{HistoryBuffer, Vec};
+    use core::fmt::Write;
+    use core::ptr;
+
+    #[test]
+    fn new() {
+        let x: HistoryBuffer<u8, 4> = HistoryBuffer::new_with(1);
+        assert_eq!(x.len(), 4);
+        assert_eq!(x.as_slice(), [1; 4]);
+
+        let x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert_eq!(x.as_slice(), []);
+    }
+
+    #[test]
+    fn write() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        x.write(1);
+        x.write(4);
+        assert_eq!(x.as_slice(), [1, 4]);
+
+        x.write(5);
+        x.write(6);
+        x.write(10);
+        assert_eq!(x.as_slice(), [10, 4, 5, 6]);
+
+        x.extend([11, 12].iter());
+        assert_eq!(x.as_slice(), [10, 11, 12, 6]);
+    }
+
+    #[test]
+    fn clear() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new_with(1);
+        x.clear();
+        assert_eq!(x.as_slice(), []);
+
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        x.clear_with(1);
+        assert_eq!(x.as_slice(), [1; 4]);
+    }
+
+    #[test]
+    fn recent() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert_eq!(x.recent(), None);
+
+        x.write(1);
+        x.write(4);
+        assert_eq!(x.recent(), Some(&4));
+
+        x.write(5);
+        x.write(6);
+        x.write(10);
+        assert_eq!(x.recent(), Some(&10));
+    }
+
+    #[test]
+    fn as_slice() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+
+        assert_eq!(x.as_slice(), []);
+
+        x.extend([1, 2, 3, 4, 5].iter());
+
+        assert_eq!(x.as_slice(), [5, 2, 3, 4]);
+    }
+
+    #[test]
+    fn drain_filter() {
+        let mut x: HistoryBuffer<u8, 6> = HistoryBuffer::new();
+        x.extend([1, 2, 3, 4, 5, 6, 7, 8].iter());
+
+        let mut drained = [0; 6];
+        let mut n = 0;
+        for el in x.drain_filter(|x| x % 3 != 0) {
+            drained[n] = el;
+            n += 1;
+        }
+        assert_eq!(drained[..n], [4, 5, 7, 8]);
+        assert_eq!(x.as_slice(), [3, 6]);
+        assert_eq!(x.recent(), Some(&6));
+
+        // the buffer keeps working after a drain
+        x.extend([9, 10, 11, 12, 13].iter());
+        assert_eq!(x.len(), 6);
+        assert_eq!(x.recent(), Some(&13));
+
+        // nothing matches: the buffer stays full
+        assert_eq!(x.drain_filter(|_| false).next(), None);
+        assert_eq!(x.len(), 6);
+        assert_eq!(x.as_slice(), [6, 9, 10, 11, 12, 13]);
+    }
+
+    #[test]
+    fn drain_filter_drop() {
+        static mut COUNT: i32 = 0;
+
+        struct Droppable(u8);
+        impl Drop for Droppable {
+            fn drop(&mut self) {
+                unsafe {
+                    COUNT += 1;
+                }
+            }
+        }
+
+        {
+            let mut x: HistoryBuffer<Droppable, 4> = HistoryBuffer::new();
+            for i in 0..6 {
+                x.write(Droppable(i));
+            }
+            assert_eq!(unsafe { COUNT }, 2);
+
+            // stop after the first match: the unvisited elements, matching or not, are kept
+            let first = x.drain_filter(|x| x.0 % 2 == 1).next().unwrap();
+            assert_eq!(first.0, 3);
+            drop(first);
+            assert_eq!(unsafe { COUNT }, 3);
+
+            assert_eq!(x.len(), 3);
+            let order: [u8; 3] = [x.as_slice()[0].0, x.as_slice()[1].0, x.as_slice()[2].0];
+            assert_eq!(order, [2, 4, 5]);
+        }
+
+        assert_eq!(unsafe { COUNT }, 6);
+    }
+
+    #[test]
+    fn sequence_gaps() {
+        let mut x: HistoryBuffer<u8, 6> = HistoryBuffer::new();
+        assert_eq!(x.sequence_gaps().next(), None);
+
+        x.extend([250, 251, 252, 253, 254, 255, 0, 1].iter());
+        assert_eq!(x.sequence_gaps().next(), None);
+
+        x.extend([2, 4, 5].iter());
+        let mut gaps = x.sequence_gaps();
+        assert_eq!(gaps.next(), Some((2, 4)));
+        assert_eq!(gaps.next(), None);
+    }
+
+    #[test]
+    fn to_q15() {
+        let mut x: HistoryBuffer<f32, 4> = HistoryBuffer::new();
+        let mut y = HistoryBuffer::new_with(1);
+
+        x.to_q15(&mut y);
+        assert_eq!(y.len(), 0);
+
+        x.extend([0.0, 0.5, -0.5, 0.25, -1.0].iter());
+        x.to_q15(&mut y);
+        assert_eq!(y.len(), 4);
+        assert_eq!(y.recent(), Some(&-32768));
+        assert_eq!(
+            y.decimate_into::<4>().as_slice(),
+            [16384, -16384, 8192, -32768]
+        );
+
+        // saturation and rounding
+        x.extend([1.0, 3.0, -2.0, 1.6e-5].iter());
+        x.to_q15(&mut y);
+        assert_eq!(y.decimate_into::<4>().as_slice(), [32767, 32767, -32768, 1]);
+    }
+
+    #[test]
+    fn summary() {
+        let mut x: HistoryBuffer<i16, 4> = HistoryBuffer::new();
+        assert_eq!(std::format!("{}", x.summary()), "len=0 capacity=4");
+        assert_eq!(std::format!("{}", x.numeric_summary()), "len=0 capacity=4");
+
+        x.extend([-3, 1, 2, 8, 5].iter());
+        assert_eq!(std::format!("{}", x.summary()), "len=4 capacity=4 recent=5");
+        assert_eq!(
+            std::format!("{}", x.numeric_summary()),
+            "len=4 capacity=4 recent=5 mean=4"
+        );
+    }
+
+    #[test]
+    fn summary_mean() {
+        let mut x: HistoryBuffer<i64, 4> = HistoryBuffer::new();
+        x.extend([-3, -2, -2, -2].iter());
+        assert_eq!(x.numeric_summary().mean, Some(-2));
+        x.extend([-3, -3].iter());
+        // -2.5 rounds up
+        assert_eq!(x.numeric_summary().mean, Some(-2));
+
+        let mut x: HistoryBuffer<u64, 2> = HistoryBuffer::new();
+        x.extend([u64::MAX, u64::MAX].iter());
+        assert_eq!(x.numeric_summary().mean, Some(u64::MAX));
+
+        let mut x: HistoryBuffer<usize, 2> = HistoryBuffer::new();
+        x.extend([1, 2].iter());
+        assert_eq!(x.numeric_summary().mean, Some(2));
+
+        let mut x: HistoryBuffer<f32, 4> = HistoryBuffer::new();
+        x.extend([1.0, 2.0].iter());
+        assert_eq!(x.numeric_summary().mean, Some(1.5));
+        assert_eq!(
+            std::format!("{}", x.numeric_summary()),
+            "len=2 capacity=4 recent=2.0 mean=1.5"
+        );
+    }
+
+    #[test]
+    fn evict_hook() {
+        static mut EVICTED: [u8; 8] = [0; 8];
+        static mut COUNT: usize = 0;
+
+        fn hook(x: u8) {
+            unsafe {
+                EVICTED[COUNT] = x;
+                COUNT += 1;
+            }
+        }
+
+        let mut x: HookedHistoryBuffer<u8, 3> = HookedHistoryBuffer::new(hook);
+        x.extend([1, 2, 3].iter());
+        assert_eq!(unsafe { COUNT }, 0);
+
+        x.extend([4, 5].iter());
+        assert_eq!(unsafe { COUNT }, 2);
+        assert_eq!(unsafe { &EVICTED[..2] }, [1, 2]);
+
+        // the hook survives a `clear`, which doesn't evict anything
+        x.clear();
+        assert_eq!(unsafe { COUNT }, 2);
+        x.extend([6, 7, 8, 9].iter());
+        assert_eq!(unsafe { COUNT }, 3);
+        assert_eq!(unsafe { EVICTED[2] }, 6);
+
+        // the hook is handed back with the buffer
+        let (buf, hook) = x.into_parts();
+        assert_eq!(buf.as_slice(), [9, 7, 8]);
+        let mut x: HookedHistoryBuffer<u8, 3> = HookedHistoryBuffer::new(hook);
+        x.extend([1, 2, 3, 4].iter());
+        assert_eq!(unsafe { COUNT }, 4);
+    }
+
+    #[test]
+    fn get_clamped() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert_eq!(x.get_clamped(0), None);
+        assert_eq!(x.get_clamped(-1), None);
+
+        x.extend([1, 2, 3, 4, 5, 6].iter());
+        assert_eq!(x.get_clamped(0), Some(&3));
+        assert_eq!(x.get_clamped(3), Some(&6));
+        assert_eq!(x.get_clamped(-1), Some(&6));
+        assert_eq!(x.get_clamped(-4), Some(&3));
+        assert_eq!(x.get_clamped(4), Some(&6));
+        assert_eq!(x.get_clamped(isize::MAX), Some(&6));
+        assert_eq!(x.get_clamped(-5), Some(&3));
+        assert_eq!(x.get_clamped(isize::MIN), Some(&3));
+    }
+
+    #[test]
+    fn merge_sorted() {
+        let mut x: HistoryBuffer<u8, 5> = HistoryBuffer::new();
+        let mut y: HistoryBuffer<u8, 5> = HistoryBuffer::new();
+        assert_eq!(x.merge_sorted(&y).next(), None);
+
+        x.extend([0, 0, 1, 3, 3, 8].iter());
+        y.extend([2, 3, 9].iter());
+
+        let mut merged = [0; 8];
+        for (n, &el) in x.merge_sorted(&y).enumerate() {
+            merged[n] = el;
+        }
+        assert_eq!(merged, [0, 1, 2, 3, 3, 3, 8, 9]);
+
+        // one side empty
+        let z: HistoryBuffer<u8, 5> = HistoryBuffer::new();
+        assert_eq!(z.merge_sorted(&y).count(), 3);
+        assert_eq!(y.merge_sorted(&z).count(), 3);
+    }
+
+    #[test]
+    fn zero_crossing_rate() {
+        let mut x: HistoryBuffer<f32, 5> = HistoryBuffer::new();
+        assert_eq!(x.zero_crossing_rate(), None);
+        x.write(1.);
+        assert_eq!(x.zero_crossing_rate(), None);
+
+        x.extend([1., -1., 1., -1., 1., -1.].iter());
+        assert_eq!(x.zero_crossing_rate(), Some(1.0));
+
+        x.extend([2., 2., 2., 2., 2.].iter());
+        assert_eq!(x.zero_crossing_rate(), Some(0.0));
+
+        // zero counts as positive
+        x.extend([-1., 0., 1., 0., -3.].iter());
+        assert_eq!(x.zero_crossing_rate(), Some(0.5));
+    }
+
+    #[test]
+    fn fill_cycling() {
+        let mut x: HistoryBuffer<u8, 7> = HistoryBuffer::new();
+        x.write(9);
+
+        x.fill_cycling([].iter().cloned());
+        assert_eq!(x.as_slice(), [9]);
+
+        x.fill_cycling([1, 2, 3].iter().cloned());
+        assert_eq!(x.len(), 7);
+        assert_eq!(x.recent(), Some(&1));
+        assert_eq!(x.decimate_into::<7>().as_slice(), [1, 2, 3, 1, 2, 3, 1]);
+
+        x.fill_cycling(5..15);
+        assert_eq!(x.decimate_into::<7>().as_slice(), [5, 6, 7, 8, 9, 10, 11]);
+    }
+
+    #[test]
+    fn checkpoint() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        x.extend([1, 2, 3, 4, 5].iter());
+
+        let snapshot = x.checkpoint();
+        x.extend([6, 7].iter());
+        assert_eq!(x.as_slice(), [5, 6, 7, 4]);
+
+        x.restore(&snapshot);
+        assert_eq!(x.as_slice(), [5, 2, 3, 4]);
+        assert_eq!(x.recent(), Some(&5));
+        assert_eq!(x.get_clamped(0), Some(&2));
+
+        // writing after a restore continues from the restored cursor
+        x.write(8);
+        assert_eq!(x.as_slice(), [5, 8, 3, 4]);
+
+        // restoring a partially filled snapshot
+        let snapshot = HistoryBuffer::new().checkpoint();
+        x.restore(&snapshot);
+        assert_eq!(x.len(), 0);
+    }
+
+    #[test]
+    fn sum_abs_energy() {
+        let mut x: HistoryBuffer<f32, 4> = HistoryBuffer::new();
+        assert_eq!(x.sum_abs(), None);
+        assert_eq!(x.energy(), None);
+
+        x.extend([7.0, -1.5, 2.0, -0.5, 3.0].iter());
+        assert_eq!(x.sum_abs(), Some(1.5 + 2.0 + 0.5 + 3.0));
+        assert_eq!(x.energy(), Some(2.25 + 4.0 + 0.25 + 9.0));
+    }
+
+    #[test]
+    fn write_repeated() {
+        static mut CLONES: usize = 0;
+
+        #[derive(Debug, PartialEq)]
+        struct Counted(u8);
+        impl Clone for Counted {
+            fn clone(&self) -> Self {
+                unsafe {
+                    CLONES += 1;
+                }
+                Counted(self.0)
+            }
+        }
+
+        let mut x: HistoryBuffer<Counted, 4> = HistoryBuffer::new();
+        x.write_repeated(Counted(0), 0);
+        assert_eq!(x.len(), 0);
+
+        x.write_repeated(Counted(1), 3);
+        assert_eq!(unsafe { CLONES }, 2);
+        assert_eq!(x.as_slice(), [Counted(1), Counted(1), Counted(1)]);
+
+        x.write_repeated(Counted(2), usize::MAX);
+        assert_eq!(unsafe { CLONES }, 2 + 3);
+        assert_eq!(x.len(), 4);
+        assert_eq!(
+            x.as_slice(),
+            [Counted(2), Counted(2), Counted(2), Counted(2)]
+        );
+    }
+
+    #[test]
+    fn ordered() {
+        use super::Ordered;
+
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert!(matches!(x.ordered(), Ordered::Borrowed(&[])));
+
+        x.extend([1, 2, 3, 4].iter());
+        match x.ordered() {
+            Ordered::Borrowed(slice) => assert_eq!(slice, [1, 2, 3, 4]),
+            Ordered::Owned(_) => panic!("unexpected copy"),
+        }
+
+        x.extend([5, 6].iter());
+        match x.ordered() {
+            Ordered::Borrowed(_) => panic!("unexpected borrow"),
+            Ordered::Owned(vec) => assert_eq!(vec, [3, 4, 5, 6]),
+        }
+
+        x.extend([7, 8].iter());
+        assert!(matches!(x.ordered(), Ordered::Borrowed(&[5, 6, 7, 8])));
+    }
+
+    #[test]
+    fn transitions() {
+        let mut x: HistoryBuffer<u8, 8> = HistoryBuffer::new();
+        assert_eq!(x.transitions(), 0);
+        x.write(1);
+        assert_eq!(x.transitions(), 0);
+
+        // five runs, spanning the wrap around
+        x.extend([9, 9, 7, 1, 1, 2, 2, 2, 3, 1].iter());
+        assert_eq!(x.transitions(), 4);
+
+        x.extend([1; 8].iter());
+        assert_eq!(x.transitions(), 0);
+    }
+
+    #[test]
+    fn pad_with_recent() {
+        let mut x: HistoryBuffer<u8, 6> = HistoryBuffer::new();
+        x.pad_with_recent();
+        assert_eq!(x.len(), 0);
+
+        x.extend([1, 2, 3].iter());
+        x.pad_with_recent();
+        assert_eq!(x.len(), 6);
+        assert_eq!(x.recent(), Some(&3));
+        assert_eq!(x.as_slice(), [1, 2, 3, 3, 3, 3]);
+
+        // full buffers are left alone
+        x.write(4);
+        x.pad_with_recent();
+        assert_eq!(x.as_slice(), [4, 2, 3, 3, 3, 3]);
+    }
+
+    #[test]
+    fn mean_u32() {
+        let mut x: HistoryBuffer<u32, 4> = HistoryBuffer::new();
+        assert_eq!(x.mean_u32(), None);
+
+        x.extend([1, 2].iter());
+        assert_eq!(x.mean_u32(), Some(2));
+        x.write(2);
+        assert_eq!(x.mean_u32(), Some(2));
+        x.write(2);
+        assert_eq!(x.mean_u32(), Some(2));
+        x.write(3);
+        assert_eq!(x.mean_u32(), Some(2));
+        x.write(4);
+        assert_eq!(x.mean_u32(), Some(3));
+
+        // no overflow
+        x.extend([u32::MAX; 4].iter());
+        assert_eq!(x.mean_u32(), Some(u32::MAX));
+
+        let mut y: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+        y.extend([0, 0, 1].iter());
+        assert_eq!(y.mean_u32(), Some(0));
+    }
+
+    #[test]
+    fn pairs() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert_eq!(x.pairs().next(), None);
+        assert_eq!(x.pairs_rev().next(), None);
+        x.write(1);
+        assert_eq!(x.pairs().next(), None);
+        assert_eq!(x.pairs_rev().next(), None);
+
+        // stored as [5, 2, 3, 4]
+        x.extend([2, 3, 4, 5].iter());
+
+        let mut pairs = x.pairs();
+        assert_eq!(pairs.next(), Some((&2, &3)));
+        assert_eq!(pairs.next(), Some((&3, &4)));
+        assert_eq!(pairs.next(), Some((&4, &5)));
+        assert_eq!(pairs.next(), None);
+
+        let mut pairs = x.pairs_rev();
+        assert_eq!(pairs.next(), Some((&5, &4)));
+        assert_eq!(pairs.next(), Some((&4, &3)));
+        assert_eq!(pairs.next(), Some((&3, &2)));
+        assert_eq!(pairs.next(), None);
+    }
+
+    #[test]
+    fn repair() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert!(!x.repair());
+
+        x.extend([1, 2, 3, 4, 5].iter());
+        assert!(!x.repair());
+        assert_eq!(x.as_slice(), [5, 2, 3, 4]);
+
+        // a full buffer stays full
+        x.write_at = 7;
+        assert!(x.repair());
+        assert_eq!(x.len(), 4);
+        assert_eq!(x.as_slice(), [5, 2, 3, 4]);
+        x.write(6);
+        assert_eq!(x.as_slice(), [5, 2, 3, 6]);
+
+        // a partially filled buffer keeps the elements before the clamped position
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        x.extend([1, 2, 3].iter());
+        x.write_at = 9;
+        assert!(x.repair());
+        assert_eq!(x.as_slice(), [1, 2, 3]);
+        x.write(4);
+        assert_eq!(x.as_slice(), [1, 2, 3, 4]);
+        assert!(x.filled);
+
+        let mut y: HistoryBuffer<u8, 0> = HistoryBuffer::new();
+        assert!(!y.repair());
+        y.write_at = 1;
+        assert!(y.repair());
+        assert_eq!(y.len(), 0);
+    }
+
+    #[test]
+    fn run_length_encode() {
+        let mut x: HistoryBuffer<u8, 8> = HistoryBuffer::new();
+        assert_eq!(x.run_length_encode(), []);
+
+        x.extend([9, 1, 1, 1, 2, 3, 3, 1, 1, 1].iter());
+        assert_eq!(x.run_length_encode(), [(1, 2), (2, 1), (3, 2), (1, 3)]);
+
+        x.extend([4; 8].iter());
+        assert_eq!(x.run_length_encode(), [(4, 8)]);
+
+        x.extend([1, 2, 3, 4, 5, 6, 7, 8].iter());
+        assert_eq!(x.run_length_encode().len(), 8);
+    }
+
+    #[test]
+    fn diff() {
+        let mut x: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+        let mut y: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+        assert_eq!(x.diff(&y).next(), None);
+
+        x.extend([0, 5, 7, 9, 11].iter());
+        y.extend([4, 4, 4].iter());
+        let mut diff = x.diff(&y);
+        assert_eq!(diff.next(), Some(1));
+        assert_eq!(diff.next(), Some(3));
+        assert_eq!(diff.next(), Some(5));
+        assert_eq!(diff.next(), None);
+        drop(diff);
+
+        y.extend([1, 2, 3].iter());
+        let mut diff = y.diff(&x);
+        assert_eq!(diff.next(), Some(-1));
+        assert_eq!(diff.next(), Some(-6));
+        assert_eq!(diff.next(), Some(-7));
+        assert_eq!(diff.next(), Some(-8));
+        assert_eq!(diff.next(), None);
+    }
+
+    #[test]
+    fn apply_window() {
+        let mut x: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+        x.extend([1, 2, 3, 4, 5, 6].iter());
+
+        x.apply_window(&[1, 1, 1, 1]);
+        assert_eq!(x.as_slice(), [5, 6, 3, 4]);
+
+        // oldest of the window is 4, newest is 6
+        x.apply_window(&[10, 100, 1000]);
+        assert_eq!(x.as_slice(), [500, 6000, 3, 40]);
+
+        x.apply_window::<0>(&[]);
+        assert_eq!(x.as_slice(), [500, 6000, 3, 40]);
+
+        let mut y: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+        y.extend([1, 2].iter());
+        y.apply_window(&[3, 4]);
+        assert_eq!(y.as_slice(), [3, 8]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn apply_window_too_short() {
+        let mut x: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+        x.extend([1, 2].iter());
+        x.apply_window(&[1, 1, 1]);
+    }
+
+    #[test]
+    fn uninit_count() {
+        let mut x: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+        assert_eq!(x.as_slice(), []);
+        assert_eq!(x.uninit_count(), 3);
+
+        for i in 1..=5 {
+            x.write(i);
+            assert_eq!(x.as_slice().len() + x.uninit_count(), x.capacity());
+        }
+
+        assert_eq!(x.as_slice(), [4, 5, 3]);
+        assert_eq!(x.uninit_count(), 0);
+    }
+
+    #[test]
+    fn map_reduce() {
+        let mut x: HistoryBuffer<i32, 4> = HistoryBuffer::new();
+        assert_eq!(x.map_reduce(7, |&v| v, |a, b| a + b), 7);
+
+        x.extend([3, 8, 1, 6, 4, 9].iter());
+
+        let mean = 6;
+        assert_eq!(
+            x.map_reduce(0, |&v| (v - mean) * (v - mean), |a, b| a.max(b)),
+            x.ordered()
+                .iter()
+                .map(|&v| (v - mean) * (v - mean))
+                .fold(0, |a, b| a.max(b)),
+        );
+
+        // order sensitive reduction
+        assert_eq!(
+            x.map_reduce(0, |&v| v, |a, b| a * 10 + b),
+            x.ordered().iter().fold(0, |a, &b| a * 10 + b),
+        );
+        assert_eq!(x.map_reduce(0, |&v| v, |a, b| a * 10 + b), 1649);
+    }
+
+    #[test]
+    fn resample() {
+        let mut x: HistoryBuffer<f64, 4> = HistoryBuffer::new();
+        x.extend([5.0, 0.0, 3.0, 6.0, 9.0].iter());
+
+        let up: HistoryBuffer<f64, 7> = x.resample();
+        assert_eq!(up.as_slice(), [0.0, 1.5, 3.0, 4.5, 6.0, 7.5, 9.0]);
+
+        let down: HistoryBuffer<f64, 3> = x.resample();
+        assert_eq!(down.as_slice(), [0.0, 4.5, 9.0]);
+
+        let one: HistoryBuffer<f64, 1> = x.resample();
+        assert_eq!(one.as_slice(), [0.0]);
+
+        let same: HistoryBuffer<f64, 4> = x.resample();
+        assert_eq!(same.as_slice(), [0.0, 3.0, 6.0, 9.0]);
+
+        let mut y: HistoryBuffer<f64, 4> = HistoryBuffer::new();
+        let empty: HistoryBuffer<f64, 3> = y.resample();
+        assert_eq!(empty.len(), 0);
+
+        y.write(2.0);
+        let flat: HistoryBuffer<f64, 3> = y.resample();
+        assert_eq!(flat.as_slice(), [2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn recent_eq() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        let mut y: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+        assert!(x.recent_eq(&y, 0));
+        assert!(!x.recent_eq(&y, 1));
+
+        x.extend([7, 1, 2, 3, 4, 5].iter());
+        y.extend([6, 3, 4, 5].iter());
+        assert!(x.recent_eq(&y, 3));
+        assert!(y.recent_eq(&x, 3));
+        assert!(!x.recent_eq(&y, 4));
+
+        y.write(6);
+        assert!(!x.recent_eq(&y, 1));
+        assert!(x.recent_eq(&y, 0));
+
+        let mut z: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        z.extend([4, 5].iter());
+        assert!(x.recent_eq(&z, 2));
+        assert!(!x.recent_eq(&z, 3));
+    }
+
+    #[test]
+    fn for_each() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        let mut seen: Vec<u8, 4> = Vec::new();
+        x.for_each(|&v| seen.push(v).unwrap());
+        assert_eq!(seen, []);
+
+        x.extend([1, 2, 3].iter());
+        x.for_each(|&v| seen.push(v).unwrap());
+        assert_eq!(seen, [1, 2, 3]);
+
+        x.extend([4, 5, 6].iter());
+        seen.clear();
+        x.for_each(|&v| seen.push(v).unwrap());
+        assert_eq!(seen, [3, 4, 5, 6]);
+
+        x.write(7);
+        x.write(8);
+        seen.clear();
+        x.for_each(|&v| seen.push(v).unwrap());
+        assert_eq!(seen, [5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn outliers() {
+        let mut x: HistoryBuffer<i32, 10> = HistoryBuffer::new();
+        assert_eq!(x.outliers(2.0).next(), None);
+
+        x.extend([1, 1, 1].iter());
+        assert_eq!(x.outliers(2.0).next(), None);
+
+        // mean 1.9, standard deviation 2.7, so the spike has a z-score of 3
+        x.extend([1, 1, 1, 1, 1, 1, 1, 10, 1, 1].iter());
+        let mut outliers = x.outliers(2.0);
+        assert_eq!(outliers.next(), Some(7));
+        assert_eq!(outliers.next(), None);
+        drop(outliers);
+
+        assert_eq!(x.outliers(3.5).next(), None);
+
+        x.write(-10);
+        let mut outliers = x.outliers(2.0);
+        assert_eq!(outliers.next(), Some(6));
+        assert_eq!(outliers.next(), Some(9));
+        assert_eq!(outliers.next(), None);
+    }
+
+    #[test]
+    fn swap_with_slice() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        x.extend([1, 2, 3, 4, 5, 6].iter());
+
+        let mut other = [10, 20, 30, 40];
+        x.swap_with_slice(&mut other);
+        assert_eq!(other, [3, 4, 5, 6]);
+        assert_eq!(x.as_slice(), [30, 40, 10, 20]);
+        assert_eq!(x.recent(), Some(&40));
+
+        let mut y: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        y.extend([1, 2].iter());
+        let mut other = [8, 9];
+        y.swap_with_slice(&mut other);
+        assert_eq!(other, [1, 2]);
+        assert_eq!(y.as_slice(), [8, 9]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_with_slice_mismatch() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        x.extend([1, 2].iter());
+        x.swap_with_slice(&mut [1, 2, 3]);
+    }
+
+    #[test]
+    fn write_power_of_two() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        let mut y: HistoryBuffer<u8, 5> = HistoryBuffer::new();
+
+        for i in 0..3 {
+            x.write(i);
+            y.write(i);
+        }
+        assert_eq!(x.len(), 3);
+
+        x.write(3);
+        assert_eq!(x.len(), 4);
+        assert_eq!(x.as_slice(), [0, 1, 2, 3]);
+        assert_eq!(x.recent(), Some(&3));
+
+        for i in 4..11 {
+            x.write(i);
+            y.write(i);
+            assert_eq!(x.recent(), Some(&i));
+            assert_eq!(y.recent(), Some(&i));
+        }
+        assert_eq!(x.as_slice(), [8, 9, 10, 7]);
+        assert_eq!(y.as_slice(), [6, 7, 8, 9, 10]);
+
+        let mut z: HistoryBuffer<u8, 1> = HistoryBuffer::new();
+        z.write(1);
+        assert_eq!(z.len(), 1);
+        z.write(2);
+        assert_eq!(z.as_slice(), [2]);
+    }
+
+    #[test]
+    fn retain_indexed() {
+        let mut x: HistoryBuffer<u8, 8> = HistoryBuffer::new();
+        x.extend([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10].iter());
+
+        let mut visited: Vec<(usize, u8), 8> = Vec::new();
+        x.retain_indexed(|i, &v| {
+            visited.push((i, v)).unwrap();
+            i % 2 == 0
+        });
+        assert_eq!(
+            visited,
+            [
+                (0, 3),
+                (1, 4),
+                (2, 5),
+                (3, 6),
+                (4, 7),
+                (5, 8),
+                (6, 9),
+                (7, 10)
+            ]
+        );
+        assert_eq!(x.as_slice(), [3, 5, 7, 9]);
+        assert_eq!(x.recent(), Some(&9));
+
+        x.write(11);
+        assert_eq!(x.as_slice(), [3, 5, 7, 9, 11]);
+    }
+
+    #[test]
+    fn retain_indexed_drop() {
+        static mut COUNT: i32 = 0;
+
+        struct Droppable;
+        impl Drop for Droppable {
+            fn drop(&mut self) {
+                unsafe {
+                    COUNT += 1;
+                }
+            }
+        }
+
+        let mut x: HistoryBuffer<Droppable, 4> = HistoryBuffer::new();
+        x.extend([Droppable, Droppable, Droppable, Droppable]);
+
+        x.retain_indexed(|i, _| i < 1);
+        assert_eq!(unsafe { COUNT }, 3);
+        assert_eq!(x.len(), 1);
+
+        drop(x);
+        assert_eq!(unsafe { COUNT }, 4);
+    }
+
+    #[test]
+    fn try_as_ordered_slice() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert_eq!(x.try_as_ordered_slice(), Ok(&[][..]));
+
+        x.extend([1, 2, 3, 4].iter());
+        assert_eq!(x.try_as_ordered_slice(), Ok(&[1, 2, 3, 4][..]));
+
+        x.write(5);
+        assert_eq!(x.try_as_ordered_slice(), Err(WrappedError));
+
+        x.extend([6, 7, 8].iter());
+        assert_eq!(x.try_as_ordered_slice(), Ok(&[5, 6, 7, 8][..]));
+    }
+
+    #[test]
+    fn make_contiguous() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert_eq!(x.make_contiguous(), []);
+
+        x.extend([1, 2].iter());
+        assert_eq!(x.make_contiguous(), [1, 2]);
+
+        x.extend([3, 4, 5].iter());
+        x.make_contiguous()[0] = 0;
+        assert_eq!(x.try_as_ordered_slice(), Ok(&[0, 3, 4, 5][..]));
+
+        x.write(6);
+        assert_eq!(x.try_as_ordered_slice(), Err(WrappedError));
+        assert_eq!(x.make_contiguous(), [3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn write_notify_full() {
+        let mut x: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+
+        for _ in 0..2 {
+            let fired: Vec<bool, 8> = (0..8).map(|i| x.write_notify_full(i)).collect();
+            assert_eq!(
+                fired,
+                [false, false, true, false, false, false, false, false]
+            );
+            x.clear();
+        }
+
+        x.write(1);
+        x.write(2);
+        assert!(x.write_notify_full(3));
+        assert!(!x.write_notify_full(4));
+    }
+
+    #[test]
+    fn make_contiguous_rotates_once() {
+        static mut ROTATIONS: i32 = 0;
+
+        // counts how many times the buffer was rotated, by tracking the moves of the element
+        // stored in the first slot of the backing array
+        struct Tracked(u8);
+        impl Tracked {
+            fn check(x: &HistoryBuffer<Tracked, 4>, first: &mut u8) {
+                let current = x.as_slice()[0].0;
+                if current != *first {
+                    unsafe { ROTATIONS += 1 };
+                    *first = current;
+                }
+            }
+        }
+
+        let mut x: HistoryBuffer<Tracked, 4> = HistoryBuffer::new();
+        x.extend((1..=6).map(Tracked));
+        let mut first = x.as_slice()[0].0;
+
+        for _ in 0..3 {
+            let ordered = x.make_contiguous();
+            assert_eq!(ordered.iter().map(|t| t.0).sum::<u8>(), 3 + 4 + 5 + 6);
+            assert_eq!(ordered[0].0, 3);
+            assert_eq!(x.write_at, 0);
+            Tracked::check(&x, &mut first);
+        }
+        assert_eq!(unsafe { ROTATIONS }, 1);
+
+        x.write(Tracked(7));
+        for _ in 0..3 {
+            assert_eq!(x.make_contiguous()[0].0, 4);
+            Tracked::check(&x, &mut first);
+        }
+        assert_eq!(unsafe { ROTATIONS }, 2);
+    }
 
-#[cfg(test)]
-mod tests {
-    use crate::HistoryBuffer;
+    #[cfg(feature = "embedded-io-impl")]
+    struct MockReader<'a> {
+        data: &'a [u8],
+        chunk: usize,
+    }
+
+    #[cfg(feature = "embedded-io-impl")]
+    impl embedded_io::ErrorType for MockReader<'_> {
+        type Error = embedded_io::ErrorKind;
+    }
+
+    #[cfg(feature = "embedded-io-impl")]
+    impl embedded_io::Read for MockReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            if self.chunk == 0 {
+                return Err(embedded_io::ErrorKind::Other);
+            }
 
+            let n = buf.len().min(self.chunk).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[cfg(feature = "embedded-io-impl")]
     #[test]
-    fn new() {
-        let x: HistoryBuffer<u8, 4> = HistoryBuffer::new_with(1);
+    fn fill_from_read() {
+        let data: Vec<u8, 11> = (1..=11).collect();
+
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        let mut reader = MockReader {
+            data: &data,
+            chunk: 3,
+        };
+        assert_eq!(x.fill_from_read(&mut reader, 2), Ok(2));
+        assert_eq!(x.as_slice(), [1, 2]);
+
+        // stops at EOF
+        assert_eq!(x.fill_from_read(&mut reader, 100), Ok(9));
         assert_eq!(x.len(), 4);
-        assert_eq!(x.as_slice(), [1; 4]);
+        assert_eq!(x.recent(), Some(&11));
+        assert_eq!(x.ordered().as_ref(), [8, 9, 10, 11]);
+        assert_eq!(x.fill_from_read(&mut reader, 100), Ok(0));
 
-        let x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
-        assert_eq!(x.as_slice(), []);
+        let mut reader = MockReader {
+            data: &data,
+            chunk: 0,
+        };
+        assert_eq!(
+            x.fill_from_read(&mut reader, 1),
+            Err(embedded_io::ErrorKind::Other)
+        );
+        assert_eq!(x.fill_from_read(&mut reader, 0), Ok(0));
+
+        // reads everything into a partially filled buffer
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        x.write(0);
+        let mut reader = MockReader {
+            data: &data[..5],
+            chunk: 2,
+        };
+        assert_eq!(x.fill_from_read(&mut reader, usize::MAX), Ok(5));
+        assert_eq!(x.ordered().as_ref(), [2, 3, 4, 5]);
     }
 
     #[test]
-    fn write() {
+    fn scaled() {
+        let x: HistoryBuffer<f32, 4> = HistoryBuffer::new();
+        let y = x.scaled(2.0);
+        assert_eq!(y.len(), 0);
+
+        let mut x: HistoryBuffer<f32, 4> = HistoryBuffer::new();
+        x.extend([1.0, 2.0, 3.0, 4.0, 5.0].iter());
+        let y = x.scaled(0.5);
+        assert_eq!(y.as_slice(), [2.5, 1.0, 1.5, 2.0]);
+        assert_eq!(y.recent(), Some(&2.5));
+        assert_eq!(y.ordered().as_ref(), [1.0, 1.5, 2.0, 2.5]);
+        assert_eq!(x.as_slice(), [5.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn circular_shift() {
+        let mut x: HistoryBuffer<u8, 5> = HistoryBuffer::new();
+        x.circular_shift(3);
+        assert_eq!(x.len(), 0);
+
+        x.extend([1, 2, 3].iter());
+        x.circular_shift(1);
+        assert_eq!(&*x.ordered(), [3, 1, 2]);
+        x.circular_shift(-1);
+        assert_eq!(&*x.ordered(), [1, 2, 3]);
+        x.circular_shift(-4);
+        assert_eq!(&*x.ordered(), [2, 3, 1]);
+        x.circular_shift(6);
+        assert_eq!(&*x.ordered(), [2, 3, 1]);
+        assert_eq!(x.len(), 3);
+
+        x.extend([4, 5, 6, 7].iter());
+        assert_eq!(&*x.ordered(), [1, 4, 5, 6, 7]);
+        x.circular_shift(2);
+        assert_eq!(&*x.ordered(), [6, 7, 1, 4, 5]);
+        assert_eq!(x.recent(), Some(&5));
+        x.circular_shift(-2);
+        assert_eq!(&*x.ordered(), [1, 4, 5, 6, 7]);
+        x.circular_shift(isize::MIN);
+        assert_eq!(x.len(), 5);
+
+        x.write(8);
+        assert_eq!(x.recent(), Some(&8));
+    }
+
+    #[test]
+    fn assert_fits() {
+        fn needs_two<const N: usize>(x: &HistoryBuffer<u8, N>) -> usize {
+            assert_fits!(HistoryBuffer<u8, N>, 2);
+            x.capacity()
+        }
+
+        assert_fits!(HistoryBuffer<u8, 4>, 4);
+        assert_eq!(needs_two(&HistoryBuffer::<u8, 2>::new()), 2);
+        assert_eq!(needs_two(&HistoryBuffer::<u8, 8>::new()), 8);
+    }
+
+    #[test]
+    fn recent_run_len() {
         let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert_eq!(x.recent_run_len(), 0);
+
         x.write(1);
+        assert_eq!(x.recent_run_len(), 1);
+
+        x.extend([2, 3, 3, 3].iter());
+        assert_eq!(x.recent_run_len(), 3);
+
+        x.write(3);
+        assert_eq!(x.recent_run_len(), 4);
+
         x.write(4);
-        assert_eq!(x.as_slice(), [1, 4]);
+        assert_eq!(x.recent_run_len(), 1);
+    }
+
+    #[test]
+    fn oldest_ordered() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert_eq!(x.oldest_ordered().next(), None);
+        assert_eq!(x.recent_ordered().next(), None);
+
+        x.extend([1, 2, 3].iter());
+        let v: Vec<u8, 4> = x.oldest_ordered().copied().collect();
+        assert_eq!(v, [1, 2, 3]);
+        let v: Vec<u8, 4> = x.recent_ordered().copied().collect();
+        assert_eq!(v, [3, 2, 1]);
+
+        x.extend([4, 5, 6].iter());
+        let v: Vec<u8, 4> = x.oldest_ordered().copied().collect();
+        assert_eq!(v, [3, 4, 5, 6]);
+        let v: Vec<u8, 4> = x.recent_ordered().copied().collect();
+        assert_eq!(v, [6, 5, 4, 3]);
+
+        let mut iter = x.oldest_ordered();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next_back(), Some(&6));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.next(), Some(&4));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn as_slices() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert_eq!(x.as_slices(), (&[][..], &[][..]));
+
+        x.extend([1, 2].iter());
+        assert_eq!(x.as_slices(), (&[1, 2][..], &[][..]));
+
+        x.extend([3, 4].iter());
+        assert_eq!(x.as_slices(), (&[1, 2, 3, 4][..], &[][..]));
 
         x.write(5);
+        assert_eq!(x.as_slices(), (&[2, 3, 4][..], &[5][..]));
+
+        let (older, newer) = x.as_mut_slices();
+        older[0] = 20;
+        newer[0] = 50;
+        assert_eq!(x.as_slices(), (&[20, 3, 4][..], &[50][..]));
+        assert_eq!(x.recent(), Some(&50));
+
+        x.extend([6, 7, 8].iter());
+        assert_eq!(x.as_slices(), (&[50, 6, 7, 8][..], &[][..]));
+
+        let y: HistoryBuffer<u8, 0> = HistoryBuffer::new();
+        assert_eq!(y.as_slices(), (&[][..], &[][..]));
+    }
+
+    #[test]
+    fn recent_mut_and_oldest() {
+        let mut x: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+        assert_eq!(x.recent_mut(), None);
+        assert_eq!(x.oldest(), None);
+        assert_eq!(x.oldest_mut(), None);
+
+        x.write(1);
+        assert_eq!(x.oldest(), Some(&1));
+        *x.recent_mut().unwrap() = 2;
+        assert_eq!(x.oldest(), Some(&2));
+
+        x.extend([3, 4].iter());
+        assert_eq!(x.oldest(), Some(&2));
+        *x.oldest_mut().unwrap() = 5;
+        *x.recent_mut().unwrap() = 6;
+        assert_eq!(x.as_slice(), [5, 3, 6]);
+
+        x.write(7);
+        assert_eq!(x.oldest(), Some(&3));
+        assert_eq!(x.recent_mut(), Some(&mut 7));
+
+        x.write(8);
+        *x.oldest_mut().unwrap() = 9;
+        assert_eq!(x.as_slice(), [7, 8, 9]);
+        assert_eq!(x.oldest(), Some(&9));
+    }
+
+    #[test]
+    fn write_evict() {
+        let mut x: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+        assert_eq!(x.write_evict(1), None);
+        assert_eq!(x.write_evict(2), None);
+        assert_eq!(x.write_evict(3), None);
+        assert_eq!(x.write_evict(4), Some(1));
+        assert_eq!(x.write_evict(5), Some(2));
+        assert_eq!(x.as_slice(), [4, 5, 3]);
+
         x.write(6);
-        x.write(10);
-        assert_eq!(x.as_slice(), [10, 4, 5, 6]);
+        assert_eq!(x.write_evict(7), Some(4));
+    }
 
-        x.extend([11, 12].iter());
-        assert_eq!(x.as_slice(), [10, 11, 12, 6]);
+    #[test]
+    fn into_iter() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        x.extend([1, 2, 3, 4, 5, 6].iter());
+
+        let mut v: Vec<u8, 4> = Vec::new();
+        for el in &x {
+            v.push(*el).unwrap();
+        }
+        assert_eq!(v, [3, 4, 5, 6]);
+
+        for el in &mut x {
+            *el *= 2;
+        }
+        assert_eq!(x.iter().copied().collect::<Vec<u8, 4>>(), [6, 8, 10, 12]);
+        assert_eq!(x.iter_mut().next_back(), Some(&mut 12));
+
+        let v: Vec<u8, 4> = x.into_iter().collect();
+        assert_eq!(v, [6, 8, 10, 12]);
+
+        let mut y: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        y.extend([1, 2].iter());
+        let mut iter = y.into_iter();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some(2));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), None);
     }
 
     #[test]
-    fn clear() {
-        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new_with(1);
+    fn into_iter_drop() {
+        static mut COUNT: i32 = 0;
+
+        struct Droppable;
+        impl Drop for Droppable {
+            fn drop(&mut self) {
+                unsafe {
+                    COUNT += 1;
+                }
+            }
+        }
+
+        let mut x: HistoryBuffer<Droppable, 3> = HistoryBuffer::new();
+        x.extend([Droppable, Droppable, Droppable, Droppable, Droppable]);
+        assert_eq!(unsafe { COUNT }, 2);
+
+        let mut iter = x.into_iter();
+        drop(iter.next());
+        assert_eq!(unsafe { COUNT }, 3);
+        drop(iter);
+        assert_eq!(unsafe { COUNT }, 5);
+    }
+
+    #[test]
+    fn get() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        assert_eq!(x.get(0), None);
+
+        x.extend([1, 2].iter());
+        assert_eq!(x.get(0), Some(&2));
+        assert_eq!(x.get(1), Some(&1));
+        assert_eq!(x.get(2), None);
+
+        x.extend([3, 4, 5, 6].iter());
+        assert_eq!(x.get(0), Some(&6));
+        assert_eq!(x.get(3), Some(&3));
+        assert_eq!(x.get(4), None);
+        assert_eq!([x[0], x[1], x[2], x[3]], [6, 5, 4, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        x.extend([1, 2].iter());
+        let _ = x[2];
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        use serde::de::{value::Error, value::SeqDeserializer, Deserialize};
+
+        let de = SeqDeserializer::<_, Error>::new([1u8, 2, 3].iter().copied());
+        let x: HistoryBuffer<u8, 4> = HistoryBuffer::deserialize(de).unwrap();
+        assert_eq!(x.as_slice(), [1, 2, 3]);
+
+        let de = SeqDeserializer::<_, Error>::new([1u8, 2, 3, 4, 5, 6].iter().copied());
+        let x: HistoryBuffer<u8, 4> = HistoryBuffer::deserialize(de).unwrap();
+        assert_eq!(
+            x.oldest_ordered().copied().collect::<Vec<u8, 4>>(),
+            [3, 4, 5, 6]
+        );
+        assert_eq!(x.recent(), Some(&6));
+    }
+
+    #[test]
+    fn rolling_sum() {
+        let mut x: RollingSum<i32, 3> = RollingSum::new();
+        assert_eq!(x.sum(), 0);
+        assert_eq!(x.mean(), None);
+
+        x.write(4);
+        assert_eq!(x.sum(), 4);
+        assert_eq!(x.mean(), Some(4.0));
+
+        for i in 5..20 {
+            x.write(i);
+            assert_eq!(x.sum(), x.oldest_ordered().sum::<i32>());
+        }
+        assert_eq!(x.sum(), 17 + 18 + 19);
+        assert_eq!(x.mean(), Some(18.0));
+
+        x.resync();
+        assert_eq!(x.sum(), 17 + 18 + 19);
+
         x.clear();
-        assert_eq!(x.as_slice(), []);
+        assert_eq!(x.sum(), 0);
+        assert_eq!(x.len(), 0);
+        x.write(-1);
+        assert_eq!(x.sum(), -1);
+        assert_eq!(x.into_inner().as_slice(), [-1]);
+    }
+
+    #[test]
+    fn clone() {
+        let mut x: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+        assert_eq!(x.clone().len(), 0);
+
+        x.extend([1, 2].iter());
+        let y = x.clone();
+        assert_eq!(y.as_slice(), [1, 2]);
+
+        x.extend([3, 4].iter());
+        let mut y = x.clone();
+        assert_eq!(y.as_slice(), x.as_slice());
+        assert_eq!(y.recent(), Some(&4));
+        y.write(5);
+        assert_eq!(y.as_slice(), [4, 5, 3]);
+        assert_eq!(x.as_slice(), [4, 2, 3]);
+    }
+
+    #[test]
+    fn clone_drop() {
+        static mut COUNT: i32 = 0;
+
+        #[derive(Clone)]
+        struct Droppable;
+        impl Drop for Droppable {
+            fn drop(&mut self) {
+                unsafe {
+                    COUNT += 1;
+                }
+            }
+        }
+
+        let mut x: HistoryBuffer<Droppable, 3> = HistoryBuffer::new();
+        x.extend([Droppable, Droppable]);
+        drop(x.clone());
+        assert_eq!(unsafe { COUNT }, 2);
+
+        x.extend([Droppable, Droppable]);
+        assert_eq!(unsafe { COUNT }, 3);
+        drop(x.clone());
+        assert_eq!(unsafe { COUNT }, 6);
+    }
+
+    #[test]
+    fn debug() {
+        let mut x: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+        let mut s: crate::String<32> = crate::String::new();
+        write!(s, "{:?}", x).unwrap();
+        assert_eq!(s, "[]");
+
+        x.extend([1, 2, 3, 4].iter());
+        s.clear();
+        write!(s, "{:?}", x).unwrap();
+        assert_eq!(s, "[2, 3, 4]");
+    }
+
+    #[test]
+    fn partial_eq() {
+        let mut x: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+        let mut y: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+        let mut z: HistoryBuffer<u8, 5> = HistoryBuffer::new();
+        assert_eq!(x, y);
+        assert_eq!(x, z);
+
+        x.extend([1, 2, 3, 4].iter());
+        y.extend([2, 3, 4].iter());
+        z.extend([2, 3, 4].iter());
+        assert_ne!(x.as_slice(), y.as_slice());
+        assert_eq!(x, y);
+        assert_eq!(x, z);
 
+        y.write(5);
+        assert_ne!(x, y);
+        z.write(5);
+        assert_ne!(x, z);
+    }
+
+    #[test]
+    fn drain() {
         let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
-        x.clear_with(1);
-        assert_eq!(x.as_slice(), [1; 4]);
+        assert_eq!(x.drain().next(), None);
+
+        x.extend([1, 2, 3, 4, 5, 6].iter());
+        let v: Vec<u8, 4> = x.drain().collect();
+        assert_eq!(v, [3, 4, 5, 6]);
+        assert_eq!(x.len(), 0);
+
+        x.extend([1, 2, 3].iter());
+        let mut drain = x.drain();
+        assert_eq!(drain.len(), 3);
+        assert_eq!(drain.next_back(), Some(3));
+        assert_eq!(drain.next(), Some(1));
+        drop(drain);
+        assert_eq!(x.len(), 0);
+
+        x.write(7);
+        assert_eq!(x.as_slice(), [7]);
     }
 
     #[test]
-    fn recent() {
+    fn drain_drop() {
+        static mut COUNT: i32 = 0;
+
+        struct Droppable;
+        impl Drop for Droppable {
+            fn drop(&mut self) {
+                unsafe {
+                    COUNT += 1;
+                }
+            }
+        }
+
+        let mut x: HistoryBuffer<Droppable, 3> = HistoryBuffer::new();
+        x.extend([Droppable, Droppable, Droppable, Droppable]);
+        assert_eq!(unsafe { COUNT }, 1);
+
+        let mut drain = x.drain();
+        drop(drain.next());
+        assert_eq!(unsafe { COUNT }, 2);
+        drop(drain);
+        assert_eq!(unsafe { COUNT }, 4);
+
+        drop(x);
+        assert_eq!(unsafe { COUNT }, 4);
+    }
+
+    #[test]
+    fn from_array() {
+        let x = HistoryBuffer::from([1, 2, 3]);
+        assert_eq!(x.len(), 3);
+        assert_eq!(x.oldest(), Some(&1));
+        assert_eq!(x.recent(), Some(&3));
+
+        let mut x = HistoryBuffer::from([crate::String::<4>::from("a"), "b".into()]);
+        x.write("c".into());
+        assert_eq!(x.oldest().map(|s| s.as_str()), Some("b"));
+    }
+
+    #[test]
+    fn extend_from_copy_slice() {
         let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
-        assert_eq!(x.recent(), None);
+        let mut y: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+
+        let writes: [&[u8]; 6] = [
+            &[],
+            &[1, 2],
+            &[3, 4],
+            &[5, 6, 7],
+            &[8, 9, 10, 11, 12, 13],
+            &[14],
+        ];
+        for &w in writes.iter() {
+            x.extend_from_copy_slice(w);
+            y.extend_from_slice(w);
+            assert_eq!(x, y);
+        }
+
+        let mut z: HistoryBuffer<u8, 0> = HistoryBuffer::new();
+        z.extend_from_copy_slice(&[1, 2]);
+        assert_eq!(z.len(), 0);
+    }
+
+    #[test]
+    fn hooked_extend_from_slice() {
+        static mut EVICTED: u32 = 0;
+
+        fn hook(x: u8) {
+            unsafe { EVICTED += x as u32 };
+        }
+
+        let mut x: HookedHistoryBuffer<u8, 2> = HookedHistoryBuffer::new(hook);
+        x.extend_from_slice(&[1, 2, 3, 4]);
+        assert_eq!(unsafe { EVICTED }, 1 + 2);
+        assert_eq!(x.as_slice(), [3, 4]);
+    }
+
+    #[test]
+    fn new_with_const() {
+        static X: HistoryBuffer<u8, 4> = HistoryBuffer::new_with(7);
+        const Y: HistoryBuffer<u8, 2> = HistoryBuffer::new_with(1);
+
+        assert_eq!(X.as_slice(), [7; 4]);
+        assert_eq!(Y.len(), 2);
+    }
+
+    #[test]
+    fn downsample() {
+        let mut x: HistoryBuffer<u8, 6> = HistoryBuffer::new();
+        assert_eq!(x.downsample(2).next(), None);
+
+        x.extend([1, 2, 3, 4, 5, 6, 7, 8, 9].iter());
+        let v: Vec<u8, 6> = x.downsample(2).copied().collect();
+        assert_eq!(v, [4, 6, 8]);
+        let v: Vec<u8, 6> = x.downsample(1).copied().collect();
+        assert_eq!(v, [4, 5, 6, 7, 8, 9]);
+        let v: Vec<u8, 6> = x.downsample(10).copied().collect();
+        assert_eq!(v, [4]);
+        assert_eq!(x.downsample(4).len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn downsample_zero() {
+        let x: HistoryBuffer<u8, 6> = HistoryBuffer::new();
+        let _ = x.downsample(0);
+    }
+
+    #[test]
+    fn timestamped() {
+        let mut x: TimestampedHistoryBuffer<u32, u8, 3> = TimestampedHistoryBuffer::new();
+        assert_eq!(x.since(0).next(), None);
+
+        x.write(10, 1);
+        x.write(20, 2);
+        x.write(20, 3);
+        x.write(35, 4);
+
+        let v: Vec<(u32, u8), 3> = x.since(0).copied().collect();
+        assert_eq!(v, [(20, 2), (20, 3), (35, 4)]);
+        let v: Vec<(u32, u8), 3> = x.since(20).copied().collect();
+        assert_eq!(v, [(20, 2), (20, 3), (35, 4)]);
+        let v: Vec<(u32, u8), 3> = x.since(21).copied().collect();
+        assert_eq!(v, [(35, 4)]);
+        assert_eq!(x.since(36).next(), None);
+
+        assert_eq!(x.recent(), Some(&(35, 4)));
+        x.clear();
+        assert_eq!(x.len(), 0);
+    }
+
+    #[test]
+    fn write_with() {
+        let mut x: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+        let mut evicted: Vec<u8, 8> = Vec::new();
+
+        for i in 0..8 {
+            x.write_with(i, |old| evicted.push(old).unwrap());
+        }
+        assert_eq!(evicted, [0, 1, 2, 3, 4]);
+        assert_eq!(x.as_slice(), [6, 7, 5]);
+    }
+
+    #[test]
+    fn has_min_samples() {
+        let mut x: HistoryBuffer<u8, 3> = HistoryBuffer::new();
+        assert!(x.has_min_samples(0));
+        assert!(!x.has_min_samples(1));
 
         x.write(1);
+        x.write(2);
+        assert!(x.has_min_samples(2));
+        assert!(!x.has_min_samples(3));
+
+        x.write(3);
+        assert!(x.has_min_samples(3));
+        assert!(!x.has_min_samples(4));
+
         x.write(4);
+        assert!(x.has_min_samples(3));
+        assert!(!x.has_min_samples(4));
+    }
+
+    #[test]
+    fn parts() {
+        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        x.extend([1, 2, 3, 4, 5, 6].iter());
+
+        let (data, write_at, filled) = x.into_parts();
+        assert_eq!(write_at, 2);
+        assert!(filled);
+
+        let mut x: HistoryBuffer<u8, 4> =
+            unsafe { HistoryBuffer::from_parts(data, write_at, filled) };
+        assert_eq!(x.as_slice(), [5, 6, 3, 4]);
+        assert_eq!(x.recent(), Some(&6));
+        x.write(7);
+        assert_eq!(x.as_slice(), [5, 6, 7, 4]);
+
+        let y: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+        let (data, write_at, filled) = y.into_parts();
+        let y: HistoryBuffer<u8, 4> = unsafe { HistoryBuffer::from_parts(data, write_at, filled) };
+        assert_eq!(y.len(), 0);
+    }
+
+    #[test]
+    fn parts_drop() {
+        static mut COUNT: i32 = 0;
+
+        struct Droppable;
+        impl Drop for Droppable {
+            fn drop(&mut self) {
+                unsafe {
+                    COUNT += 1;
+                }
+            }
+        }
+
+        let mut x: HistoryBuffer<Droppable, 4> = HistoryBuffer::new();
+        x.extend([Droppable, Droppable, Droppable]);
+
+        let (data, write_at, filled) = x.into_parts();
+        assert_eq!(unsafe { COUNT }, 0);
+
+        drop(unsafe { HistoryBuffer::from_parts(data, write_at, filled) });
+        assert_eq!(unsafe { COUNT }, 3);
+    }
+
+    #[test]
+    fn assume_empty() {
+        static mut COUNT: i32 = 0;
+
+        struct Droppable(u8);
+        impl Drop for Droppable {
+            fn drop(&mut self) {
+                unsafe {
+                    COUNT += 1;
+                }
+            }
+        }
+
+        {
+            let mut x: HistoryBuffer<Droppable, 4> = HistoryBuffer::new();
+            for i in 0..6 {
+                x.write(Droppable(i));
+            }
+            assert_eq!(unsafe { COUNT }, 2);
+
+            // move every element out, then tell the buffer it no longer owns them
+            let len = x.len();
+            let ptr = x.as_slice().as_ptr();
+            let mut sum = 0;
+            for i in 0..len {
+                sum += unsafe { ptr::read(ptr.add(i)) }.0;
+            }
+            assert_eq!(sum, 2 + 3 + 4 + 5);
+            unsafe { x.assume_empty() };
+            assert_eq!(unsafe { COUNT }, 6);
+            assert_eq!(x.len(), 0);
+            assert!(x.recent().is_none());
+
+            x.write(Droppable(0));
+        }
+
+        assert_eq!(unsafe { COUNT }, 7);
+    }
+
+    #[test]
+    fn macro_repeat() {
+        let x = history_buffer![7u8; 5];
+        assert_eq!(x.capacity(), 5);
+        assert_eq!(x.len(), 5);
+        assert_eq!(x.as_slice(), [7; 5]);
+    }
+
+    #[test]
+    fn macro_list() {
+        let mut x = history_buffer![1, 2, 3, 4];
+        assert_eq!(x.capacity(), 4);
+        assert_eq!(x.len(), 4);
+        assert_eq!(x.as_slice(), [1, 2, 3, 4]);
         assert_eq!(x.recent(), Some(&4));
 
+        // the oldest element is the first one listed
         x.write(5);
-        x.write(6);
-        x.write(10);
-        assert_eq!(x.recent(), Some(&10));
+        assert_eq!(x.as_slice(), [5, 2, 3, 4]);
     }
 
     #[test]
-    fn as_slice() {
-        let mut x: HistoryBuffer<u8, 4> = HistoryBuffer::new();
+    fn decimate_into() {
+        let mut x: HistoryBuffer<u8, 10> = HistoryBuffer::new();
 
-        assert_eq!(x.as_slice(), []);
+        let y: HistoryBuffer<u8, 4> = x.decimate_into();
+        assert_eq!(y.len(), 0);
 
-        x.extend([1, 2, 3, 4, 5].iter());
+        x.extend([0, 1, 2].iter());
+        let y: HistoryBuffer<u8, 4> = x.decimate_into();
+        assert_eq!(y.as_slice(), [0, 1, 2]);
 
-        assert_eq!(x.as_slice(), [5, 2, 3, 4]);
+        // wrap around so that the chronological order differs from the storage order
+        x.extend([3, 4, 5, 6, 7, 8, 9, 10, 11, 12].iter());
+        let y: HistoryBuffer<u8, 4> = x.decimate_into();
+        assert_eq!(y.as_slice(), [3, 5, 8, 10]);
+    }
+
+    #[test]
+    fn sqrt() {
+        for &x in [0.0, 1e-300, 0.25, 1.0, 2.0, 9.0, 1234.5678, 1e300].iter() {
+            let y = super::sqrt(x);
+            assert!((y - x.sqrt()).abs() <= x.sqrt() * 1e-15);
+        }
+    }
+
+    #[test]
+    fn correlation() {
+        let mut x: HistoryBuffer<f32, 4> = HistoryBuffer::new();
+        let mut y: HistoryBuffer<f32, 4> = HistoryBuffer::new();
+
+        // empty
+        assert_eq!(x.correlation(&y), None);
+
+        x.extend([1., 2., 3., 4., 5.].iter());
+
+        // perfectly correlated, with a different wrap position
+        y.extend([5., 7., 9., 11.].iter());
+        assert!((x.correlation(&y).unwrap() - 1.0).abs() < 1e-12);
+
+        // anti-correlated
+        y.extend([-2., -3., -4., -5.].iter());
+        assert!((x.correlation(&y).unwrap() + 1.0).abs() < 1e-12);
+
+        // uncorrelated
+        y.extend([1., -1., -1., 1.].iter());
+        assert!(x.correlation(&y).unwrap().abs() < 1e-12);
+
+        // zero variance
+        y.extend([3., 3., 3., 3.].iter());
+        assert_eq!(x.correlation(&y), None);
+
+        // different lengths
+        let mut y: HistoryBuffer<f32, 4> = HistoryBuffer::new();
+        y.extend([1., 2., 3.].iter());
+        assert_eq!(x.correlation(&y), None);
+
+        // the product of the variances, around 1e401, doesn't fit in an `f64`
+        let mut x: HistoryBuffer<f64, 4> = HistoryBuffer::new();
+        let mut y: HistoryBuffer<f64, 4> = HistoryBuffer::new();
+        x.extend([1e100, 2e100, 3e100, 4e100].iter());
+        y.extend([-2e100, -4e100, -6e100, -8e100].iter());
+        assert!((x.correlation(&y).unwrap() + 1.0).abs() < 1e-12);
     }
 }
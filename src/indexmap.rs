@@ -2,7 +2,7 @@ use core::{borrow::Borrow, fmt, iter::FromIterator, mem, num::NonZeroU32, ops, s
 
 use hash32::{BuildHasher, BuildHasherDefault, FnvHasher, Hash, Hasher};
 
-use crate::Vec;
+use crate::{vec::CapacityError, Vec};
 
 /// A [`heapless::IndexMap`](./struct.IndexMap.html) using the default FNV hasher
 ///
@@ -523,6 +523,38 @@ where
         self.len() == 0
     }
 
+    /// Returns true if the map can't hold any more elements.
+    ///
+    /// Computes in **O(1)** time.
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut a = FnvIndexMap::<_, _, 2>::new();
+    /// a.insert(1, "a").unwrap();
+    /// assert!(!a.is_full());
+    /// a.insert(2, "b").unwrap();
+    /// assert!(a.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Returns the number of elements that can still be inserted into the map.
+    ///
+    /// Computes in **O(1)** time.
+    ///
+    /// ```
+    /// use heapless::FnvIndexMap;
+    ///
+    /// let mut a = FnvIndexMap::<_, _, 16>::new();
+    /// a.insert(1, "a").unwrap();
+    /// assert_eq!(a.remaining_capacity(), 15);
+    /// ```
+    pub fn remaining_capacity(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
     /// Remove all key-value pairs in the map, while preserving its capacity.
     ///
     /// Computes in **O(n)** time.
@@ -703,6 +735,35 @@ where
             .map(|(probe, found)| self.core.remove_found(probe, found).1)
     }
 
+    /// Creates a map from the key-value pairs of an iterator, returning an error if they don't
+    /// fit.
+    ///
+    /// Like [`insert`](struct.IndexMap.html#method.insert), a pair whose key is already in the
+    /// map replaces the previous value, but still needs a free slot to be inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{vec::CapacityError, FnvIndexMap};
+    ///
+    /// let map = FnvIndexMap::<_, _, 4>::try_from_iter([(1, "a"), (2, "b")]).unwrap();
+    /// assert_eq!(map[&2], "b");
+    ///
+    /// let pairs = (0..5).map(|i| (i, i));
+    /// assert_eq!(FnvIndexMap::<_, _, 4>::try_from_iter(pairs).err(), Some(CapacityError));
+    /// ```
+    pub fn try_from_iter<I>(iterable: I) -> Result<Self, CapacityError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        S: Default,
+    {
+        let mut map = IndexMap::default();
+        for (k, v) in iterable {
+            map.insert(k, v).map_err(|_| CapacityError)?;
+        }
+        Ok(map)
+    }
+
     /* Private API */
     /// Return probe (indices) and position (entries)
     fn find<Q>(&self, key: &Q) -> Option<(usize, usize)>
@@ -970,4 +1031,15 @@ mod tests {
             assert!(a == b);
         }
     }
+
+    #[test]
+    fn try_from_iter() {
+        let map =
+            FnvIndexMap::<_, _, 2>::try_from_iter([(1, 'a'), (2, 'b'), (1, 'c')]).unwrap_err();
+        assert_eq!(map, crate::vec::CapacityError);
+
+        let map = FnvIndexMap::<_, _, 4>::try_from_iter([(1, 'a'), (2, 'b'), (1, 'c')]).unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&1], 'c');
+    }
 }
@@ -299,6 +299,38 @@ where
         self.map.is_empty()
     }
 
+    /// Returns `true` if the set can't hold any more elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut v: FnvIndexSet<_, 2> = FnvIndexSet::new();
+    /// v.insert(1).unwrap();
+    /// assert!(!v.is_full());
+    /// v.insert(2).unwrap();
+    /// assert!(v.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        self.map.is_full()
+    }
+
+    /// Returns the number of elements that can still be inserted into the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::FnvIndexSet;
+    ///
+    /// let mut v: FnvIndexSet<_, 16> = FnvIndexSet::new();
+    /// v.insert(1).unwrap();
+    /// assert_eq!(v.remaining_capacity(), 15);
+    /// ```
+    pub fn remaining_capacity(&self) -> usize {
+        self.map.remaining_capacity()
+    }
+
     /// Clears the set, removing all values.
     ///
     /// # Examples
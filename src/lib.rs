@@ -44,12 +44,14 @@
 //! List of currently implemented data structures:
 //!
 //! - [`BinaryHeap`](binary_heap/struct.BinaryHeap.html) -- priority queue
+//! - [`CString`](c_string/struct.CString.html) -- nul-terminated C string (`c-string` feature)
+//! - [`HistoryBuffer`](histbuf/struct.HistoryBuffer.html) -- similar to a write-only ring buffer
 //! - [`IndexMap`](struct.IndexMap.html) -- hash table
 //! - [`IndexSet`](struct.IndexSet.html) -- hash set
 //! - [`LinearMap`](struct.LinearMap.html)
 //! - [`Pool`](pool/struct.Pool.html) -- lock-free memory pool
 //! - [`String`](struct.String.html)
-//! - [`Vec`](struct.Vec.html)
+//! - [`Vec`](type.Vec.html)
 //! - [`mpmc::Q*`](mpmc/index.html) -- multiple producer multiple consumer lock-free queue
 //! - [`spsc::Queue`](spsc/struct.Queue.html) -- single producer single consumer lock-free queue
 //!
@@ -57,13 +59,39 @@
 //!
 //! The `heapless` crate provides the following optional Cargo features:
 //!
-//! - `ufmt-impl`: Implement [`ufmt_write::uWrite`] for `String<N>` and `Vec<u8, N>`
+//! - `serde`: Implement `Serialize` and `Deserialize` for the containers, and add
+//!   `de::truncate` for deserializing oversized input by dropping what doesn't fit
+//! - `ufmt-impl`: Implement [`ufmt_write::uWrite`] for `String<N>` and `Vec<u8, N>`, `uDisplay`
+//!   for `String<N>`, and `uDebug` for `Vec`, `BinaryHeap`, `HistoryBuffer`, `IndexMap`,
+//!   `IndexSet` and `LinearMap`
+//! - `defmt-impl`: Implement [`defmt::Format`] for `String`, `Vec`, `IndexMap`, `LinearMap` and
+//!   `histbuf::Summary`
+//! - `embedded-io-impl`: Implement [`embedded_io::Read`] and `embedded_io::Write` for
+//!   `Vec<u8, N>`, and add `HistoryBuffer::fill_from_read` for reading from an
+//!   [`embedded_io::Read`]
+//! - `zeroize`: Implement [`zeroize::Zeroize`] for `Vec`, `String` and `LinearMap`, wiping
+//!   their whole backing storage, and add `zeroize::ZeroizingVec` and `zeroize::ZeroizingString`
+//!   which are wiped when dropped
+//! - `arbitrary`: Implement [`arbitrary::Arbitrary`] for `Vec`, `String` and `IndexMap`, for
+//!   fuzzing; the generated containers never exceed their capacity
+//! - `encoding`: Add the [`encoding`](encoding/index.html) module, for hex and Base64 encoding
+//!   into `String` and decoding into `Vec`
+//! - `ryu`: Support `f32` and `f64` in `String::from_num` and `String::push_num`, using the
+//!   [`ryu`] algorithm
+//! - `c-string`: Add [`CString`](c_string/struct.CString.html), which is built on `core::ffi`
+//!   and so requires Rust 1.64 or newer
 //!
 //! [`ufmt_write::uWrite`]: https://docs.rs/ufmt-write/
+//! [`defmt::Format`]: https://docs.rs/defmt/
+//! [`embedded_io::Read`]: https://docs.rs/embedded-io/
+//! [`zeroize::Zeroize`]: https://docs.rs/zeroize/
+//! [`arbitrary::Arbitrary`]: https://docs.rs/arbitrary/
+//! [`ryu`]: https://docs.rs/ryu/
 //!
 //! # Minimum Supported Rust Version (MSRV)
 //!
-//! This crate is guaranteed to compile on stable Rust 1.51 and up with its default set of features.
+//! This crate is guaranteed to compile on stable Rust 1.61 and up with its default set of features.
+//! The `c-string` feature requires Rust 1.64.
 //! It *might* compile on older versions but that may change in any new patch release.
 
 #![cfg_attr(not(test), no_std)]
@@ -73,35 +101,52 @@
 #![deny(warnings)]
 
 pub use binary_heap::BinaryHeap;
+#[cfg(feature = "c-string")]
+pub use c_string::CString;
 pub use histbuf::HistoryBuffer;
 pub use indexmap::{Bucket, FnvIndexMap, IndexMap, Pos};
 pub use indexset::{FnvIndexSet, IndexSet};
 pub use linear_map::LinearMap;
-pub use string::String;
+pub use string::{FromUtf16Error, String, TruncatingWriter};
 pub use vec::Vec;
 
 // NOTE this code was last ported from v0.4.1 of the indexmap crate
-mod histbuf;
 mod indexmap;
 mod indexset;
 mod linear_map;
 mod string;
-mod vec;
 
-#[cfg(feature = "serde")]
-mod de;
 #[cfg(feature = "serde")]
 mod ser;
 
 pub mod binary_heap;
+#[cfg(feature = "c-string")]
+pub mod c_string;
+#[cfg(feature = "serde")]
+pub mod de;
+#[cfg(feature = "encoding")]
+pub mod encoding;
+pub mod histbuf;
 #[cfg(all(has_cas, feature = "cas"))]
 pub mod mpmc;
 #[cfg(all(has_cas, feature = "cas"))]
 pub mod pool;
 #[cfg(has_atomics)]
 pub mod spsc;
+pub mod vec;
+#[cfg(feature = "zeroize")]
+pub mod zeroize;
 
 #[cfg(feature = "ufmt-impl")]
 mod ufmt;
 
+#[cfg(feature = "defmt-impl")]
+mod defmt;
+
+#[cfg(feature = "embedded-io-impl")]
+mod embedded_io;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
 mod sealed;
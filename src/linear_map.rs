@@ -201,6 +201,42 @@ where
         self.len() == 0
     }
 
+    /// Returns true if the map can't hold any more elements
+    ///
+    /// Computes in **O(1)** time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::LinearMap;
+    ///
+    /// let mut a: LinearMap<_, _, 2> = LinearMap::new();
+    /// a.insert(1, "a").unwrap();
+    /// assert!(!a.is_full());
+    /// a.insert(2, "b").unwrap();
+    /// assert!(a.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        self.buffer.is_full()
+    }
+
+    /// Returns the number of elements that can still be inserted into the map
+    ///
+    /// Computes in **O(1)** time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::LinearMap;
+    ///
+    /// let mut a: LinearMap<_, _, 8> = LinearMap::new();
+    /// a.insert(1, "a").unwrap();
+    /// assert_eq!(a.remaining_capacity(), 7);
+    /// ```
+    pub fn remaining_capacity(&self) -> usize {
+        self.buffer.remaining_capacity()
+    }
+
     /// An iterator visiting all key-value pairs in arbitrary order.
     ///
     /// # Examples
@@ -82,7 +82,7 @@
 //!
 //! [0]: http://www.1024cores.net/home/lock-free-algorithms/queues/bounded-mpmc-queue
 
-use core::{cell::UnsafeCell, mem::MaybeUninit};
+use core::{cell::UnsafeCell, cmp, mem::MaybeUninit};
 
 #[cfg(armv6m)]
 use atomic_polyfill::{AtomicU8, Ordering};
@@ -127,6 +127,22 @@ impl<T> Q2<T> {
             )
         }
     }
+
+    /// Returns `true` if the queue is full
+    ///
+    /// Other contexts may use the queue concurrently, so the result can be outdated by the time
+    /// it's returned.
+    pub fn is_full(&self) -> bool {
+        self.remaining_capacity() == 0
+    }
+
+    /// Returns the number of elements that can still be enqueued
+    ///
+    /// Other contexts may use the queue concurrently, so the result can be outdated by the time
+    /// it's returned.
+    pub fn remaining_capacity(&self) -> usize {
+        remaining_capacity(&self.dequeue_pos, &self.enqueue_pos, Self::MASK)
+    }
 }
 
 unsafe impl<T> Sync for Q2<T> where T: Send {}
@@ -168,6 +184,22 @@ impl<T> Q4<T> {
             )
         }
     }
+
+    /// Returns `true` if the queue is full
+    ///
+    /// Other contexts may use the queue concurrently, so the result can be outdated by the time
+    /// it's returned.
+    pub fn is_full(&self) -> bool {
+        self.remaining_capacity() == 0
+    }
+
+    /// Returns the number of elements that can still be enqueued
+    ///
+    /// Other contexts may use the queue concurrently, so the result can be outdated by the time
+    /// it's returned.
+    pub fn remaining_capacity(&self) -> usize {
+        remaining_capacity(&self.dequeue_pos, &self.enqueue_pos, Self::MASK)
+    }
 }
 
 unsafe impl<T> Sync for Q4<T> where T: Send {}
@@ -218,6 +250,22 @@ impl<T> Q8<T> {
             )
         }
     }
+
+    /// Returns `true` if the queue is full
+    ///
+    /// Other contexts may use the queue concurrently, so the result can be outdated by the time
+    /// it's returned.
+    pub fn is_full(&self) -> bool {
+        self.remaining_capacity() == 0
+    }
+
+    /// Returns the number of elements that can still be enqueued
+    ///
+    /// Other contexts may use the queue concurrently, so the result can be outdated by the time
+    /// it's returned.
+    pub fn remaining_capacity(&self) -> usize {
+        remaining_capacity(&self.dequeue_pos, &self.enqueue_pos, Self::MASK)
+    }
 }
 
 unsafe impl<T> Sync for Q8<T> where T: Send {}
@@ -276,6 +324,22 @@ impl<T> Q16<T> {
             )
         }
     }
+
+    /// Returns `true` if the queue is full
+    ///
+    /// Other contexts may use the queue concurrently, so the result can be outdated by the time
+    /// it's returned.
+    pub fn is_full(&self) -> bool {
+        self.remaining_capacity() == 0
+    }
+
+    /// Returns the number of elements that can still be enqueued
+    ///
+    /// Other contexts may use the queue concurrently, so the result can be outdated by the time
+    /// it's returned.
+    pub fn remaining_capacity(&self) -> usize {
+        remaining_capacity(&self.dequeue_pos, &self.enqueue_pos, Self::MASK)
+    }
 }
 
 unsafe impl<T> Sync for Q16<T> where T: Send {}
@@ -350,6 +414,22 @@ impl<T> Q32<T> {
             )
         }
     }
+
+    /// Returns `true` if the queue is full
+    ///
+    /// Other contexts may use the queue concurrently, so the result can be outdated by the time
+    /// it's returned.
+    pub fn is_full(&self) -> bool {
+        self.remaining_capacity() == 0
+    }
+
+    /// Returns the number of elements that can still be enqueued
+    ///
+    /// Other contexts may use the queue concurrently, so the result can be outdated by the time
+    /// it's returned.
+    pub fn remaining_capacity(&self) -> usize {
+        remaining_capacity(&self.dequeue_pos, &self.enqueue_pos, Self::MASK)
+    }
 }
 
 unsafe impl<T> Sync for Q32<T> where T: Send {}
@@ -456,6 +536,22 @@ impl<T> Q64<T> {
             )
         }
     }
+
+    /// Returns `true` if the queue is full
+    ///
+    /// Other contexts may use the queue concurrently, so the result can be outdated by the time
+    /// it's returned.
+    pub fn is_full(&self) -> bool {
+        self.remaining_capacity() == 0
+    }
+
+    /// Returns the number of elements that can still be enqueued
+    ///
+    /// Other contexts may use the queue concurrently, so the result can be outdated by the time
+    /// it's returned.
+    pub fn remaining_capacity(&self) -> usize {
+        remaining_capacity(&self.dequeue_pos, &self.enqueue_pos, Self::MASK)
+    }
 }
 
 unsafe impl<T> Sync for Q64<T> where T: Send {}
@@ -474,6 +570,15 @@ impl<T> Cell<T> {
     }
 }
 
+fn remaining_capacity(dequeue_pos: &AtomicU8, enqueue_pos: &AtomicU8, mask: u8) -> usize {
+    let dequeue = dequeue_pos.load(Ordering::Relaxed);
+    let enqueue = enqueue_pos.load(Ordering::Relaxed);
+    // NOTE the two positions are loaded separately, so a stale `enqueue` may appear to be behind
+    // `dequeue`; the capacity is at most 64, so the wrapped difference fits in an `i8`
+    let len = cmp::max(enqueue.wrapping_sub(dequeue) as i8, 0) as usize;
+    (usize::from(mask) + 1).saturating_sub(len)
+}
+
 unsafe fn dequeue<T>(buffer: *mut Cell<T>, dequeue_pos: &AtomicU8, mask: u8) -> Option<T> {
     let mut pos = dequeue_pos.load(Ordering::Relaxed);
 
@@ -551,7 +656,7 @@ unsafe fn enqueue<T>(
 
 #[cfg(test)]
 mod tests {
-    use super::Q2;
+    use super::{Q2, Q4};
 
     #[test]
     fn sanity() {
@@ -565,6 +670,33 @@ mod tests {
         assert_eq!(q.dequeue(), None);
     }
 
+    #[test]
+    fn remaining_capacity() {
+        let q = Q4::new();
+        assert_eq!(q.remaining_capacity(), 4);
+        assert!(!q.is_full());
+
+        for i in 0..4 {
+            q.enqueue(i).unwrap();
+        }
+        assert_eq!(q.remaining_capacity(), 0);
+        assert!(q.is_full());
+
+        assert_eq!(q.dequeue(), Some(0));
+        assert_eq!(q.remaining_capacity(), 1);
+
+        // across the wrap-around of the positions
+        let q = Q2::new();
+        for _ in 0..255 {
+            q.enqueue(0).unwrap();
+            q.dequeue().unwrap();
+        }
+        q.enqueue(0).unwrap();
+        assert_eq!(q.remaining_capacity(), 1);
+        q.enqueue(0).unwrap();
+        assert!(q.is_full());
+    }
+
     #[test]
     fn drain_at_pos255() {
         let q = Q2::new();
@@ -132,3 +132,116 @@ pub mod binary_heap {
         }
     }
 }
+
+/// Sealed traits and implementations for `vec`
+pub mod vec {
+    mod private {
+        pub trait Sealed {}
+    }
+
+    /// The type used to store the length of a `Vec`: `u8`, `u16`, `u32` or `usize`
+    ///
+    /// This trait is sealed; it's only exported so that code generic over the length type can
+    /// name it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{vec::LenType, Vec};
+    ///
+    /// fn checksum<L: LenType, const N: usize>(frame: &Vec<u8, N, L>) -> u8 {
+    ///     frame.iter().fold(0, |acc, b| acc ^ b)
+    /// }
+    ///
+    /// let frame: Vec<u8, 8, u8> = Vec::from_slice(&[1, 2, 4]).unwrap();
+    /// assert_eq!(checksum(&frame), 7);
+    /// ```
+    pub trait LenType: private::Sealed + Copy {
+        #[doc(hidden)]
+        const ZERO: Self;
+
+        #[doc(hidden)]
+        const MAX: usize;
+
+        #[doc(hidden)]
+        fn from_usize(x: usize) -> Self;
+
+        #[doc(hidden)]
+        fn into_usize(self) -> usize;
+    }
+
+    macro_rules! impl_len_type {
+        ($($ty:ty),+) => {
+            $(
+                impl private::Sealed for $ty {}
+
+                impl LenType for $ty {
+                    const ZERO: Self = 0;
+
+                    const MAX: usize = if (<$ty>::MAX as u128) < (usize::MAX as u128) {
+                        <$ty>::MAX as usize
+                    } else {
+                        usize::MAX
+                    };
+
+                    #[inline(always)]
+                    fn from_usize(x: usize) -> Self {
+                        debug_assert!(x <= Self::MAX as usize);
+                        x as Self
+                    }
+
+                    #[inline(always)]
+                    fn into_usize(self) -> usize {
+                        self as usize
+                    }
+                }
+            )+
+        };
+    }
+
+    impl_len_type!(u8, u16, u32, usize);
+
+    /// The storage of the elements of a `VecInner`: an inline array, or a borrowed or unsized
+    /// slice
+    pub trait VecStorage<T> {
+        #[doc(hidden)]
+        fn as_uninit_slice(&self) -> &[core::mem::MaybeUninit<T>];
+
+        #[doc(hidden)]
+        fn as_uninit_mut_slice(&mut self) -> &mut [core::mem::MaybeUninit<T>];
+    }
+}
+
+/// Sealed traits and implementations for `histbuf`
+pub mod histbuf {
+    /// A number whose mean can be included in a `histbuf::Summary`
+    pub trait Mean: Copy {
+        /// Returns the mean of the non-empty slice `xs`
+        #[doc(hidden)]
+        fn mean(xs: &[Self]) -> Self;
+    }
+}
+
+/// Sealed traits and implementations for `string`
+pub mod string {
+    /// A number that can be appended to a `String` without going through `core::fmt`
+    pub trait Num: Copy {
+        #[doc(hidden)]
+        fn push_to<L: super::vec::LenType, const N: usize>(
+            self,
+            s: &mut crate::String<N, L>,
+        ) -> Result<(), crate::vec::CapacityError>;
+    }
+}
+
+/// Sealed traits and implementations for `de`
+#[cfg(feature = "serde")]
+pub mod de {
+    /// A container that can be deserialized by dropping the data that doesn't fit
+    pub trait Truncate<'de>: Sized {
+        #[doc(hidden)]
+        fn deserialize_truncating<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>;
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    sealed::binary_heap::Kind as BinaryHeapKind, BinaryHeap, IndexMap, IndexSet, LinearMap, String,
-    Vec,
+    sealed::{binary_heap::Kind as BinaryHeapKind, vec::LenType},
+    BinaryHeap, HistoryBuffer, IndexMap, IndexSet, LinearMap, String, Vec,
 };
 use hash32::{BuildHasher, Hash};
 use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
@@ -41,7 +41,7 @@ where
     }
 }
 
-impl<T, const N: usize> Serialize for Vec<T, N>
+impl<T, L: LenType, const N: usize> Serialize for Vec<T, N, L>
 where
     T: Serialize,
 {
@@ -57,6 +57,22 @@ where
     }
 }
 
+impl<T, const N: usize> Serialize for HistoryBuffer<T, N>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for element in self.oldest_ordered() {
+            seq.serialize_element(element)?;
+        }
+        seq.end()
+    }
+}
+
 // Dictionaries
 
 impl<K, V, S, const N: usize> Serialize for IndexMap<K, V, S, N>
@@ -96,7 +112,7 @@ where
 
 // String containers
 
-impl<const N: usize> Serialize for String<N> {
+impl<L: LenType, const N: usize> Serialize for String<N, L> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -150,6 +150,12 @@ impl<T, const N: usize> Queue<T, N> {
         Self::increment(self.tail.load(Ordering::Relaxed)) == self.head.load(Ordering::Relaxed)
     }
 
+    /// Returns the number of elements that can still be enqueued
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
     /// Iterates from the front of the queue to the back
     pub fn iter(&self) -> Iter<'_, T, N> {
         Iter {
@@ -524,6 +530,23 @@ impl<'a, T, const N: usize> Consumer<'a, T, N> {
         self.rb.capacity()
     }
 
+    /// Returns `true` if the queue is full
+    ///
+    /// The producer may enqueue concurrently, so a `false` can be outdated by the time it's
+    /// returned, but a `true` holds until the next dequeue.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.rb.is_full()
+    }
+
+    /// Returns the number of elements that can still be enqueued
+    ///
+    /// The producer may enqueue concurrently, so this is an upper bound.
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        self.rb.remaining_capacity()
+    }
+
     /// Returns the item in the front of the queue without dequeuing, or `None` if the queue is
     /// empty
     ///
@@ -578,6 +601,23 @@ impl<'a, T, const N: usize> Producer<'a, T, N> {
     pub fn capacity(&self) -> usize {
         self.rb.capacity()
     }
+
+    /// Returns `true` if the queue is full
+    ///
+    /// The consumer may dequeue concurrently, so a `true` can be outdated by the time it's
+    /// returned, but a `false` holds until the next enqueue.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.rb.is_full()
+    }
+
+    /// Returns the number of elements that can still be enqueued
+    ///
+    /// The consumer may dequeue concurrently, so this is a lower bound.
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        self.rb.remaining_capacity()
+    }
 }
 
 #[cfg(test)]
@@ -598,6 +638,29 @@ mod tests {
         assert_eq!(rb.is_full(), true);
     }
 
+    #[test]
+    fn remaining_capacity() {
+        let mut rb: Queue<i32, 4> = Queue::new();
+        assert_eq!(rb.remaining_capacity(), 3);
+
+        rb.enqueue(1).unwrap();
+        rb.enqueue(2).unwrap();
+        assert_eq!(rb.remaining_capacity(), 1);
+
+        let (mut p, mut c) = rb.split();
+        p.enqueue(3).unwrap();
+        assert_eq!(p.remaining_capacity(), 0);
+        assert_eq!(c.remaining_capacity(), 0);
+        assert!(p.is_full());
+        assert!(c.is_full());
+
+        c.dequeue().unwrap();
+        assert_eq!(p.remaining_capacity(), 1);
+        assert_eq!(c.remaining_capacity(), 1);
+        assert!(!p.is_full());
+        assert!(!c.is_full());
+    }
+
     #[test]
     fn empty() {
         let mut rb: Queue<i32, 3> = Queue::new();
@@ -1,15 +1,36 @@
-use core::{fmt, fmt::Write, hash, ops, str};
+use core::{fmt, hash, ops, str};
 
 use hash32;
 
-use crate::Vec;
+use crate::{
+    sealed::{string::Num, vec::LenType},
+    vec::CapacityError,
+    Vec,
+};
 
 /// A fixed capacity [`String`](https://doc.rust-lang.org/std/string/struct.String.html)
-pub struct String<const N: usize> {
-    vec: Vec<u8, N>,
+///
+/// # Length type
+///
+/// As with [`Vec`], the length of the string is stored as an `L`, which defaults to `usize`.
+/// Short strings can store it as a `u8`, `u16` or `u32` instead to save RAM, as long as `N` fits
+/// in `L`:
+///
+/// ```
+/// use core::mem::size_of;
+/// use heapless::String;
+///
+/// let ssid: String<32, u8> = String::from("heapless-ap");
+/// assert_eq!(ssid, "heapless-ap");
+///
+/// assert_eq!(size_of::<String<32, u8>>(), 33);
+/// assert_eq!(size_of::<String<32>>(), 32 + size_of::<usize>());
+/// ```
+pub struct String<const N: usize, L: LenType = usize> {
+    vec: Vec<u8, N, L>,
 }
 
-impl<const N: usize> String<N> {
+impl<L: LenType, const N: usize> String<N, L> {
     /// Constructs a new, empty `String` with a fixed capacity of `N`
     ///
     /// # Examples
@@ -25,11 +46,155 @@ impl<const N: usize> String<N> {
     /// // allocate the string in a static variable
     /// static mut S: String<4> = String::new();
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity `N` doesn't fit in the length type `L`.
     #[inline]
     pub const fn new() -> Self {
         Self { vec: Vec::new() }
     }
 
+    /// Creates a string from the characters of an iterator, returning an error if they don't
+    /// fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{vec::CapacityError, String};
+    ///
+    /// let s: String<4> = String::try_from_iter("abcd".chars()).unwrap();
+    /// assert_eq!(s, "abcd");
+    ///
+    /// assert_eq!(String::<4>::try_from_iter("abcde".chars()), Err(CapacityError));
+    /// // multi-byte characters take up more than one byte of capacity
+    /// assert_eq!(String::<4>::try_from_iter("ab\u{e9}\u{e9}".chars()), Err(CapacityError));
+    /// ```
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError>
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let mut s = String::new();
+        for c in iter {
+            s.push(c).map_err(|_| CapacityError)?;
+        }
+        Ok(s)
+    }
+
+    /// Creates a string from the decimal representation of a number, returning an error if it
+    /// doesn't fit.
+    ///
+    /// Unlike `write!`, this doesn't use `core::fmt`, which makes it smaller and faster. The
+    /// output is the same as the `Display` output for integers. Floats are supported with the
+    /// `ryu` feature; they are formatted with the shortest representation that round-trips,
+    /// which may use scientific notation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{vec::CapacityError, String};
+    ///
+    /// let s: String<4> = String::from_num(-123).unwrap();
+    /// assert_eq!(s, "-123");
+    ///
+    /// assert_eq!(String::<4>::from_num(12345u32), Err(CapacityError));
+    /// ```
+    pub fn from_num<T: Num>(n: T) -> Result<Self, CapacityError> {
+        let mut s = String::new();
+        s.push_num(n)?;
+        Ok(s)
+    }
+
+    /// Decodes a UTF-16 encoded slice `v` into a `String`, returning an error if `v` contains
+    /// invalid data or if the decoded string doesn't fit.
+    ///
+    /// Invalid data is reported as [`FromUtf16Error::InvalidUtf16`], even if the string would not
+    /// have fit either.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{vec::CapacityError, FromUtf16Error, String};
+    ///
+    /// // 𝄞music
+    /// let v = &[0xD834, 0xDD1E, 0x006d, 0x0075, 0x0073, 0x0069, 0x0063];
+    /// let s: String<16> = String::from_utf16(v).unwrap();
+    /// assert_eq!(s, "𝄞music");
+    ///
+    /// // too long
+    /// assert_eq!(
+    ///     String::<4>::from_utf16(v),
+    ///     Err(FromUtf16Error::Capacity(CapacityError))
+    /// );
+    ///
+    /// // 𝄞mu<invalid>ic
+    /// let v = &[0xD834, 0xDD1E, 0x006d, 0x0075, 0xD800, 0x0069, 0x0063];
+    /// assert_eq!(
+    ///     String::<16>::from_utf16(v),
+    ///     Err(FromUtf16Error::InvalidUtf16 { position: 4 })
+    /// );
+    /// ```
+    pub fn from_utf16(v: &[u16]) -> Result<Self, FromUtf16Error> {
+        // NOTE validate first, so that invalid data is reported as such even if it doesn't fit
+        let mut position = 0;
+        for c in char::decode_utf16(v.iter().cloned()) {
+            match c {
+                Ok(c) => position += c.len_utf16(),
+                Err(_) => return Err(FromUtf16Error::InvalidUtf16 { position }),
+            }
+        }
+
+        let mut s = String::new();
+        for c in char::decode_utf16(v.iter().cloned()) {
+            // NOTE(unwrap) `v` was validated above
+            s.push(c.unwrap()).map_err(|_| CapacityError)?;
+        }
+        Ok(s)
+    }
+
+    /// Converts a slice of bytes to a `String`, replacing invalid UTF-8 sequences with
+    /// `U+FFFD REPLACEMENT CHARACTER` and truncating the result, at a [`char`] boundary, if it
+    /// doesn't fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<16> = String::from_utf8_lossy(b"Hello \xF0\x90\x80World");
+    /// assert_eq!(s, "Hello \u{FFFD}World");
+    ///
+    /// // the replacement character takes three bytes
+    /// let s: String<8> = String::from_utf8_lossy(b"Hello \xF0\x90\x80World");
+    /// assert_eq!(s, "Hello ");
+    /// ```
+    pub fn from_utf8_lossy(mut v: &[u8]) -> Self {
+        let mut s = String::new();
+        loop {
+            match str::from_utf8(v) {
+                Ok(valid) => {
+                    s.push_str_truncating(valid);
+                    break;
+                }
+                Err(e) => {
+                    let (valid, rest) = v.split_at(e.valid_up_to());
+                    // NOTE(unsafe) `from_utf8` checked that these bytes are valid
+                    let valid = unsafe { str::from_utf8_unchecked(valid) };
+                    if !s.push_str_truncating(valid) || s.push('\u{FFFD}').is_err() {
+                        break;
+                    }
+
+                    match e.error_len() {
+                        Some(len) => v = &rest[len..],
+                        // the input ends in the middle of a character
+                        None => break,
+                    }
+                }
+            }
+        }
+        s
+    }
+
     /// Converts a `String` into a byte vector.
     ///
     /// This consumes the `String`, so we do not need to copy its contents.
@@ -48,7 +213,7 @@ impl<const N: usize> String<N> {
     /// assert_eq!(&['a' as u8, 'b' as u8], &b[..]);
     /// ```
     #[inline]
-    pub fn into_bytes(self) -> Vec<u8, N> {
+    pub fn into_bytes(self) -> Vec<u8, N, L> {
         self.vec
     }
 
@@ -114,7 +279,7 @@ impl<const N: usize> String<N> {
     /// }
     /// assert_eq!(s, "olleh");
     /// ```
-    pub unsafe fn as_mut_vec(&mut self) -> &mut Vec<u8, N> {
+    pub unsafe fn as_mut_vec(&mut self) -> &mut Vec<u8, N, L> {
         &mut self.vec
     }
 
@@ -140,6 +305,15 @@ impl<const N: usize> String<N> {
         self.vec.extend_from_slice(string.as_bytes())
     }
 
+    /// Appends as much of `string` as fits, cut at a `char` boundary. Returns `true` if all of
+    /// it fit.
+    fn push_str_truncating(&mut self, string: &str) -> bool {
+        let end = floor_char_boundary(string, self.remaining_capacity());
+        // NOTE(unwrap) `end` is at most the remaining capacity
+        self.push_str(&string[..end]).unwrap();
+        end == string.len()
+    }
+
     /// Returns the maximum number of elements the String can hold
     ///
     /// # Examples
@@ -157,6 +331,45 @@ impl<const N: usize> String<N> {
         self.vec.capacity()
     }
 
+    /// Returns `true` if the `String` has no room for more bytes.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<4> = String::from("abc");
+    /// assert!(!s.is_full());
+    ///
+    /// s.push('d').unwrap();
+    /// assert!(s.is_full());
+    /// ```
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.vec.is_full()
+    }
+
+    /// Returns the number of bytes that can still be appended to this `String`.
+    ///
+    /// Note that a [`char`] may take up to 4 bytes.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<8> = String::from("abc");
+    /// assert_eq!(s.remaining_capacity(), 5);
+    /// ```
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        self.vec.remaining_capacity()
+    }
+
     /// Appends the given [`char`] to the end of this `String`.
     ///
     /// [`char`]: ../../std/primitive.char.html
@@ -188,6 +401,32 @@ impl<const N: usize> String<N> {
         }
     }
 
+    /// Appends the decimal representation of a number to the end of this `String`.
+    ///
+    /// Returns back an error, leaving the `String` untouched, if the number doesn't fit. See
+    /// [`String::from_num`] for the output format.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use heapless::{vec::CapacityError, String};
+    ///
+    /// let mut s: String<16> = String::from("x=");
+    ///
+    /// s.push_num(42u8).unwrap();
+    /// s.push_str(",y=").unwrap();
+    /// assert_eq!(s.push_num(i64::MIN), Err(CapacityError));
+    /// s.push_num(-7).unwrap();
+    ///
+    /// assert_eq!("x=42,y=-7", s);
+    /// ```
+    #[inline]
+    pub fn push_num<T: Num>(&mut self, n: T) -> Result<(), CapacityError> {
+        n.push_to(self)
+    }
+
     /// Shortens this `String` to the specified length.
     ///
     /// If `new_len` is greater than the string's current length, this has no
@@ -223,6 +462,51 @@ impl<const N: usize> String<N> {
         }
     }
 
+    /// Shortens this `String` to at most `max_len` bytes, dropping the whole [`char`] that
+    /// `max_len` falls in, if any.
+    ///
+    /// Unlike [`truncate`](Self::truncate), this never panics.
+    ///
+    /// [`char`]: ../../std/primitive.char.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<8> = String::from("caf\u{e9}");
+    ///
+    /// // `é` takes 2 bytes, starting at index 3
+    /// s.truncate_to_char_boundary(4);
+    ///
+    /// assert_eq!(s, "caf");
+    /// ```
+    pub fn truncate_to_char_boundary(&mut self, max_len: usize) {
+        let new_len = self.floor_char_boundary(max_len);
+        self.vec.truncate(new_len)
+    }
+
+    /// Returns the largest [`char`] boundary that is less than or equal to `idx`.
+    ///
+    /// Returns the length of the string if `idx` is past its end.
+    ///
+    /// [`char`]: ../../std/primitive.char.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let s: String<8> = String::from("caf\u{e9}");
+    ///
+    /// assert_eq!(s.floor_char_boundary(3), 3);
+    /// assert_eq!(s.floor_char_boundary(4), 3);
+    /// assert_eq!(s.floor_char_boundary(9), 5);
+    /// ```
+    pub fn floor_char_boundary(&self, idx: usize) -> usize {
+        floor_char_boundary(self, idx)
+    }
+
     /// Removes the last character from the string buffer and returns it.
     ///
     /// Returns [`None`] if this `String` is empty.
@@ -257,6 +541,389 @@ impl<const N: usize> String<N> {
         Some(ch)
     }
 
+    /// Inserts a character into this `String` at a byte position.
+    ///
+    /// Returns back an error if the `String` doesn't have room for the character.
+    ///
+    /// This is an *O*(*n*) operation as it requires copying every element in the
+    /// buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the `String`'s length, or if it does not
+    /// lie on a [`char`] boundary.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use heapless::{vec::CapacityError, String};
+    ///
+    /// let mut s: String<4> = String::from("ac");
+    ///
+    /// s.insert(1, 'b').unwrap();
+    /// s.insert(3, 'd').unwrap();
+    ///
+    /// assert_eq!("abcd", s);
+    /// assert_eq!(s.insert(0, 'x'), Err(CapacityError));
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, idx: usize, ch: char) -> Result<(), CapacityError> {
+        self.insert_str(idx, ch.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Inserts a string slice into this `String` at a byte position.
+    ///
+    /// Returns back an error, leaving the `String` untouched, if the `String` doesn't have
+    /// room for the whole string slice.
+    ///
+    /// This is an *O*(*n*) operation as it requires copying every element in the
+    /// buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the `String`'s length, or if it does not
+    /// lie on a [`char`] boundary.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use heapless::{vec::CapacityError, String};
+    ///
+    /// let mut s: String<16> = String::from("AT=1");
+    ///
+    /// s.insert_str(2, "+CMD").unwrap();
+    ///
+    /// assert_eq!("AT+CMD=1", s);
+    /// assert_eq!(s.insert_str(0, "too long!"), Err(CapacityError));
+    /// ```
+    pub fn insert_str(&mut self, idx: usize, string: &str) -> Result<(), CapacityError> {
+        assert!(self.is_char_boundary(idx), "index is not a char boundary");
+
+        self.vec
+            .extend_from_slice(string.as_bytes())
+            .map_err(|_| CapacityError)?;
+        // Move the new bytes from the end of the string to their position
+        self.vec[idx..].rotate_right(string.len());
+
+        Ok(())
+    }
+
+    /// Removes a [`char`] from this `String` at a byte position and returns it.
+    ///
+    /// This is an *O*(*n*) operation, as it requires copying every element in the
+    /// buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than or equal to the `String`'s length,
+    /// or if it does not lie on a [`char`] boundary.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<8> = String::from("h\u{e9}llo");
+    ///
+    /// assert_eq!(s.remove(1), '\u{e9}');
+    /// assert_eq!(s.remove(0), 'h');
+    ///
+    /// assert_eq!("llo", s);
+    /// ```
+    pub fn remove(&mut self, idx: usize) -> char {
+        let ch = match self[idx..].chars().next() {
+            Some(ch) => ch,
+            None => panic!("cannot remove a char from the end of a string"),
+        };
+
+        self.vec.drain(idx..idx + ch.len_utf8());
+
+        ch
+    }
+
+    /// Retains only the characters specified by the predicate.
+    ///
+    /// In other words, remove all characters `c` such that `f(c)` returns `false`.
+    /// This method operates in place, visiting each character exactly once in the
+    /// original order, and preserves the order of the retained characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<16> = String::from("f_o_ob_\u{e9}_r");
+    ///
+    /// s.retain(|c| c != '_');
+    ///
+    /// assert_eq!(s, "foob\u{e9}r");
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(char) -> bool,
+    {
+        // Closes the gap left by the removed characters, even if `f` panics
+        struct SetLenOnDrop<'a, const N: usize, L: LenType> {
+            s: &'a mut String<N, L>,
+            idx: usize,
+            del_bytes: usize,
+        }
+
+        impl<'a, L: LenType, const N: usize> Drop for SetLenOnDrop<'a, N, L> {
+            fn drop(&mut self) {
+                let len = self.s.len();
+                self.s
+                    .vec
+                    .copy_within(self.idx..len, self.idx - self.del_bytes);
+                self.s.vec.truncate(len - self.del_bytes);
+            }
+        }
+
+        let len = self.len();
+        let mut guard = SetLenOnDrop {
+            s: self,
+            idx: 0,
+            del_bytes: 0,
+        };
+
+        while guard.idx < len {
+            // NOTE(unsafe) `idx` lies on a char boundary and the bytes after it haven't been
+            // touched yet
+            let rest = unsafe { str::from_utf8_unchecked(&guard.s.vec[guard.idx..len]) };
+            let ch = match rest.chars().next() {
+                Some(ch) => ch,
+                None => break,
+            };
+            let ch_len = ch.len_utf8();
+
+            if !f(ch) {
+                guard.del_bytes += ch_len;
+            } else if guard.del_bytes > 0 {
+                let idx = guard.idx;
+                guard
+                    .s
+                    .vec
+                    .copy_within(idx..idx + ch_len, idx - guard.del_bytes);
+            }
+
+            guard.idx += ch_len;
+        }
+
+        drop(guard);
+    }
+
+    /// Splits the string into two at the given byte index.
+    ///
+    /// Returns a new `String` with capacity `M` holding the bytes `[at, len)`;
+    /// `self` is left holding the bytes `[0, at)`. Returns an error, leaving `self` untouched,
+    /// if the bytes `[at, len)` don't fit in the new `String`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is not on a [`char`] boundary, or if it is beyond the last
+    /// code point of the string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{vec::CapacityError, String};
+    ///
+    /// let mut key: String<16> = String::from("BAUD=115200");
+    /// let value = key.split_off::<8>(5).unwrap();
+    ///
+    /// assert_eq!(key, "BAUD=");
+    /// assert_eq!(value, "115200");
+    ///
+    /// // The tail doesn't fit
+    /// assert_eq!(key.split_off::<1>(0), Err(CapacityError));
+    /// assert_eq!(key, "BAUD=");
+    /// ```
+    pub fn split_off<const M: usize>(&mut self, at: usize) -> Result<String<M, L>, CapacityError> {
+        assert!(self.is_char_boundary(at), "index is not a char boundary");
+
+        let vec = self.vec.split_off(at)?;
+        Ok(String { vec })
+    }
+
+    /// Replaces all matches of a pattern with another string, returning the result in a new
+    /// `String` with capacity `M`.
+    ///
+    /// Returns an error if the result doesn't fit in `M` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{vec::CapacityError, String};
+    ///
+    /// let template: String<32> = String::from("{\"id\":${id},\"ack\":${id}}");
+    ///
+    /// let s = template.replace::<32>("${id}", "42").unwrap();
+    /// assert_eq!(s, "{\"id\":42,\"ack\":42}");
+    ///
+    /// assert_eq!(template.replace::<16>("${id}", "42"), Err(CapacityError));
+    /// ```
+    pub fn replace<const M: usize>(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<String<M, L>, CapacityError> {
+        let mut result = String::new();
+        let mut last_end = 0;
+        for (start, part) in self.match_indices(from) {
+            result
+                .push_str(&self[last_end..start])
+                .and_then(|()| result.push_str(to))
+                .map_err(|_| CapacityError)?;
+            last_end = start + part.len();
+        }
+        result
+            .push_str(&self[last_end..])
+            .map_err(|_| CapacityError)?;
+        Ok(result)
+    }
+
+    /// Replaces all matches of a pattern with another string of the same length, in place.
+    ///
+    /// Matches are found from the start of the string and don't overlap, as with
+    /// [`String::replace`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` and `to` have different lengths in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<16> = String::from("AT+X=$$,$$");
+    ///
+    /// s.replace_in_place("$$", "07");
+    ///
+    /// assert_eq!(s, "AT+X=07,07");
+    /// ```
+    pub fn replace_in_place(&mut self, from: &str, to: &str) {
+        assert_eq!(
+            from.len(),
+            to.len(),
+            "`from` and `to` must have the same length"
+        );
+
+        if from.is_empty() {
+            return;
+        }
+
+        let mut start = 0;
+        while let Some(idx) = self[start..].find(from) {
+            let idx = start + idx;
+            // `from` is valid UTF-8, so `to` replaces whole characters
+            self.vec[idx..idx + to.len()].copy_from_slice(to.as_bytes());
+            start = idx + to.len();
+        }
+    }
+
+    /// Returns an adapter whose [`fmt::Write`] implementation appends to this `String` and
+    /// silently discards whatever doesn't fit, instead of returning an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::fmt::Write;
+    /// use heapless::String;
+    ///
+    /// let mut s: String<8> = String::new();
+    ///
+    /// write!(s.as_truncating_writer(), "temp={}C", 1234).unwrap();
+    /// assert_eq!(s, "temp=123");
+    ///
+    /// s.clear();
+    /// write!(s.as_truncating_writer().with_ellipsis(), "temp={}C", 1234).unwrap();
+    /// assert_eq!(s, "temp=\u{2026}");
+    /// ```
+    pub fn as_truncating_writer(&mut self) -> TruncatingWriter<'_, N, L> {
+        TruncatingWriter {
+            start: self.len(),
+            string: self,
+            ellipsis: false,
+            truncated: false,
+        }
+    }
+
+    /// Converts this string to its ASCII upper case equivalent in-place.
+    ///
+    /// ASCII letters 'a' to 'z' are mapped to 'A' to 'Z', but non-ASCII letters are unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<16> = String::from("at+r\u{e9}set");
+    ///
+    /// s.make_ascii_uppercase();
+    ///
+    /// assert_eq!("AT+R\u{e9}SET", s);
+    /// ```
+    #[inline]
+    pub fn make_ascii_uppercase(&mut self) {
+        self.vec.make_ascii_uppercase()
+    }
+
+    /// Converts this string to its ASCII lower case equivalent in-place.
+    ///
+    /// ASCII letters 'A' to 'Z' are mapped to 'a' to 'z', but non-ASCII letters are unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<16> = String::from("AT+R\u{c9}SET");
+    ///
+    /// s.make_ascii_lowercase();
+    ///
+    /// assert_eq!("at+r\u{c9}set", s);
+    /// ```
+    #[inline]
+    pub fn make_ascii_lowercase(&mut self) {
+        self.vec.make_ascii_lowercase()
+    }
+
+    /// Removes leading and trailing whitespace from this `String`, in place.
+    ///
+    /// 'Whitespace' is defined as in [`str::trim`]. The remaining contents are shifted to the
+    /// start of the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::String;
+    ///
+    /// let mut s: String<16> = String::from("\t AT+GMR \r\n");
+    ///
+    /// s.trim_in_place();
+    ///
+    /// assert_eq!("AT+GMR", s);
+    /// ```
+    pub fn trim_in_place(&mut self) {
+        let start = self.len() - self.trim_start().len();
+        let end = self.trim_end().len();
+
+        if start < end {
+            self.vec.copy_within(start..end, 0);
+            self.vec.truncate(end - start);
+        } else {
+            // only whitespace
+            self.vec.clear();
+        }
+    }
+
     /// Truncates this `String`, removing all contents.
     ///
     /// While this means the `String` will have a length of zero, it does not
@@ -283,13 +950,13 @@ impl<const N: usize> String<N> {
     }
 }
 
-impl<const N: usize> Default for String<N> {
+impl<L: LenType, const N: usize> Default for String<N, L> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<'a, const N: usize> From<&'a str> for String<N> {
+impl<'a, L: LenType, const N: usize> From<&'a str> for String<N, L> {
     fn from(s: &'a str) -> Self {
         let mut new = String::new();
         new.push_str(s).unwrap();
@@ -297,17 +964,27 @@ impl<'a, const N: usize> From<&'a str> for String<N> {
     }
 }
 
-impl<const N: usize> str::FromStr for String<N> {
-    type Err = ();
+/// Parses a string, returning an error if it doesn't fit.
+///
+/// ```
+/// use heapless::{vec::CapacityError, String};
+///
+/// let s: String<8> = "AT+GMR".parse().unwrap();
+/// assert_eq!(s, "AT+GMR");
+///
+/// assert_eq!("AT+GMR".parse::<String<4>>(), Err(CapacityError));
+/// ```
+impl<L: LenType, const N: usize> str::FromStr for String<N, L> {
+    type Err = CapacityError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut new = String::new();
-        new.push_str(s)?;
+        new.push_str(s).map_err(|_| CapacityError)?;
         Ok(new)
     }
 }
 
-impl<const N: usize> Clone for String<N> {
+impl<L: LenType, const N: usize> Clone for String<N, L> {
     fn clone(&self) -> Self {
         Self {
             vec: self.vec.clone(),
@@ -315,33 +992,33 @@ impl<const N: usize> Clone for String<N> {
     }
 }
 
-impl<const N: usize> fmt::Debug for String<N> {
+impl<L: LenType, const N: usize> fmt::Debug for String<N, L> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         <str as fmt::Debug>::fmt(self, f)
     }
 }
 
-impl<const N: usize> fmt::Display for String<N> {
+impl<L: LenType, const N: usize> fmt::Display for String<N, L> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         <str as fmt::Display>::fmt(self, f)
     }
 }
 
-impl<const N: usize> hash::Hash for String<N> {
+impl<L: LenType, const N: usize> hash::Hash for String<N, L> {
     #[inline]
     fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
         <str as hash::Hash>::hash(self, hasher)
     }
 }
 
-impl<const N: usize> hash32::Hash for String<N> {
+impl<L: LenType, const N: usize> hash32::Hash for String<N, L> {
     #[inline]
     fn hash<H: hash32::Hasher>(&self, hasher: &mut H) {
         <str as hash32::Hash>::hash(self, hasher)
     }
 }
 
-impl<const N: usize> fmt::Write for String<N> {
+impl<L: LenType, const N: usize> fmt::Write for String<N, L> {
     fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
         self.push_str(s).map_err(|_| fmt::Error)
     }
@@ -351,7 +1028,92 @@ impl<const N: usize> fmt::Write for String<N> {
     }
 }
 
-impl<const N: usize> ops::Deref for String<N> {
+/// A [`fmt::Write`] adapter that discards what doesn't fit in a [`String`]
+///
+/// This `struct` is created by [`String::as_truncating_writer`]. Writing to it never fails: once
+/// the string is full, the rest of the output is dropped, cut at a `char` boundary.
+pub struct TruncatingWriter<'a, const N: usize, L: LenType = usize> {
+    string: &'a mut String<N, L>,
+    // the length of `string` when the writer was created; the ellipsis never replaces what's
+    // before it
+    start: usize,
+    ellipsis: bool,
+    truncated: bool,
+}
+
+impl<'a, L: LenType, const N: usize> TruncatingWriter<'a, N, L> {
+    /// Marks truncated output by ending it with `…` (U+2026, three bytes), replacing as many
+    /// characters as needed to make room for it.
+    ///
+    /// Only the output of this writer is replaced, not what the string held before the writer was
+    /// created; if that leaves no room for the ellipsis, the truncated output is kept as is.
+    pub fn with_ellipsis(mut self) -> Self {
+        self.ellipsis = true;
+        self
+    }
+
+    /// Returns `true` if some of the output has been discarded
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl<'a, L: LenType, const N: usize> fmt::Write for TruncatingWriter<'a, N, L> {
+    fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
+        if self.truncated || self.string.push_str_truncating(s) {
+            return Ok(());
+        }
+
+        self.truncated = true;
+        if self.ellipsis {
+            const ELLIPSIS: &str = "\u{2026}";
+
+            match N.checked_sub(ELLIPSIS.len()) {
+                Some(max_len) if max_len >= self.start => {
+                    // NOTE `start` is a char boundary, so this never cuts below it
+                    self.string.truncate_to_char_boundary(max_len);
+                    // NOTE(unwrap) we made room for it
+                    self.string.push_str(ELLIPSIS).unwrap();
+                }
+                // the ellipsis doesn't fit after the original contents
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The error returned by [`String::from_utf16`](struct.String.html#method.from_utf16)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FromUtf16Error {
+    /// The decoded string doesn't fit
+    Capacity(CapacityError),
+    /// The input contains an unpaired surrogate at `position`
+    InvalidUtf16 {
+        /// The position, in UTF-16 code units, of the first unpaired surrogate
+        position: usize,
+    },
+}
+
+impl From<CapacityError> for FromUtf16Error {
+    fn from(e: CapacityError) -> Self {
+        FromUtf16Error::Capacity(e)
+    }
+}
+
+impl fmt::Display for FromUtf16Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromUtf16Error::Capacity(e) => e.fmt(f),
+            FromUtf16Error::InvalidUtf16 { position } => {
+                write!(f, "unpaired surrogate found at position {}", position)
+            }
+        }
+    }
+}
+
+impl<L: LenType, const N: usize> ops::Deref for String<N, L> {
     type Target = str;
 
     fn deref(&self) -> &str {
@@ -359,38 +1121,40 @@ impl<const N: usize> ops::Deref for String<N> {
     }
 }
 
-impl<const N: usize> ops::DerefMut for String<N> {
+impl<L: LenType, const N: usize> ops::DerefMut for String<N, L> {
     fn deref_mut(&mut self) -> &mut str {
         self.as_mut_str()
     }
 }
 
-impl<const N: usize> AsRef<str> for String<N> {
+impl<L: LenType, const N: usize> AsRef<str> for String<N, L> {
     #[inline]
     fn as_ref(&self) -> &str {
         self
     }
 }
 
-impl<const N: usize> AsRef<[u8]> for String<N> {
+impl<L: LenType, const N: usize> AsRef<[u8]> for String<N, L> {
     #[inline]
     fn as_ref(&self) -> &[u8] {
         self.as_bytes()
     }
 }
 
-impl<const N1: usize, const N2: usize> PartialEq<String<N2>> for String<N1> {
-    fn eq(&self, rhs: &String<N2>) -> bool {
+impl<L1: LenType, L2: LenType, const N1: usize, const N2: usize> PartialEq<String<N2, L2>>
+    for String<N1, L1>
+{
+    fn eq(&self, rhs: &String<N2, L2>) -> bool {
         str::eq(&**self, &**rhs)
     }
 
-    fn ne(&self, rhs: &String<N2>) -> bool {
+    fn ne(&self, rhs: &String<N2, L2>) -> bool {
         str::ne(&**self, &**rhs)
     }
 }
 
 // String<N> == str
-impl<const N: usize> PartialEq<str> for String<N> {
+impl<L: LenType, const N: usize> PartialEq<str> for String<N, L> {
     #[inline]
     fn eq(&self, other: &str) -> bool {
         str::eq(&self[..], &other[..])
@@ -402,7 +1166,7 @@ impl<const N: usize> PartialEq<str> for String<N> {
 }
 
 // String<N> == &'str
-impl<const N: usize> PartialEq<&str> for String<N> {
+impl<L: LenType, const N: usize> PartialEq<&str> for String<N, L> {
     #[inline]
     fn eq(&self, other: &&str) -> bool {
         str::eq(&self[..], &other[..])
@@ -414,38 +1178,36 @@ impl<const N: usize> PartialEq<&str> for String<N> {
 }
 
 // str == String<N>
-impl<const N: usize> PartialEq<String<N>> for str {
+impl<L: LenType, const N: usize> PartialEq<String<N, L>> for str {
     #[inline]
-    fn eq(&self, other: &String<N>) -> bool {
+    fn eq(&self, other: &String<N, L>) -> bool {
         str::eq(&self[..], &other[..])
     }
     #[inline]
-    fn ne(&self, other: &String<N>) -> bool {
+    fn ne(&self, other: &String<N, L>) -> bool {
         str::ne(&self[..], &other[..])
     }
 }
 
 // &'str == String<N>
-impl<const N: usize> PartialEq<String<N>> for &str {
+impl<L: LenType, const N: usize> PartialEq<String<N, L>> for &str {
     #[inline]
-    fn eq(&self, other: &String<N>) -> bool {
+    fn eq(&self, other: &String<N, L>) -> bool {
         str::eq(&self[..], &other[..])
     }
     #[inline]
-    fn ne(&self, other: &String<N>) -> bool {
+    fn ne(&self, other: &String<N, L>) -> bool {
         str::ne(&self[..], &other[..])
     }
 }
 
-impl<const N: usize> Eq for String<N> {}
+impl<L: LenType, const N: usize> Eq for String<N, L> {}
 
 macro_rules! impl_from_num {
     ($num:ty, $size:expr) => {
-        impl<const N: usize> From<$num> for String<N> {
+        impl<L: LenType, const N: usize> From<$num> for String<N, L> {
             fn from(s: $num) -> Self {
-                let mut new = String::new();
-                write!(&mut new, "{}", s).unwrap();
-                new
+                String::from_num(s).unwrap()
             }
         }
     };
@@ -461,9 +1223,155 @@ impl_from_num!(u16, 5);
 impl_from_num!(u32, 10);
 impl_from_num!(u64, 20);
 
+/// Returns the largest `char` boundary of `s` that is not greater than `idx`, like the unstable
+/// `str::floor_char_boundary`
+pub(crate) fn floor_char_boundary(s: &str, idx: usize) -> usize {
+    if idx >= s.len() {
+        return s.len();
+    }
+
+    // a `char` is at most 4 bytes long, and index 0 is always a boundary
+    let mut idx = idx;
+    while !s.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
+
+/// Pairs of decimal digits, from `00` to `99`
+const DEC_DIGITS_LUT: &[u8; 200] = b"\
+      0001020304050607080910111213141516171819\
+      2021222324252627282930313233343536373839\
+      4041424344454647484950515253545556575859\
+      6061626364656667686970717273747576777879\
+      8081828384858687888990919293949596979899";
+
+macro_rules! impl_num {
+    ($($ty:ty => $uty:ty, $abs:expr, $neg:expr;)+) => {
+        $(
+            impl Num for $ty {
+                fn push_to<L: LenType, const N: usize>(
+                    self,
+                    s: &mut String<N, L>,
+                ) -> Result<(), CapacityError> {
+                    // enough for `i128::MIN`
+                    let mut buf = [0u8; 40];
+                    let mut curr = buf.len();
+                    let mut n: $uty = $abs(self);
+
+                    // two digits at a time, from the least significant
+                    while n >= 100 {
+                        let d = (n % 100) as usize * 2;
+                        n /= 100;
+                        curr -= 2;
+                        buf[curr..curr + 2].copy_from_slice(&DEC_DIGITS_LUT[d..d + 2]);
+                    }
+                    if n >= 10 {
+                        let d = n as usize * 2;
+                        curr -= 2;
+                        buf[curr..curr + 2].copy_from_slice(&DEC_DIGITS_LUT[d..d + 2]);
+                    } else {
+                        curr -= 1;
+                        buf[curr] = b'0' + n as u8;
+                    }
+                    if $neg(self) {
+                        curr -= 1;
+                        buf[curr] = b'-';
+                    }
+
+                    // NOTE(unsafe) `buf[curr..]` only contains ASCII digits and the sign
+                    s.push_str(unsafe { str::from_utf8_unchecked(&buf[curr..]) })
+                        .map_err(|_| CapacityError)
+                }
+            }
+        )+
+    };
+}
+
+impl_num! {
+    u8 => u8, |x| x, |_| false;
+    u16 => u16, |x| x, |_| false;
+    u32 => u32, |x| x, |_| false;
+    u64 => u64, |x| x, |_| false;
+    u128 => u128, |x| x, |_| false;
+    usize => usize, |x| x, |_| false;
+    i8 => u8, i8::unsigned_abs, |x| x < 0;
+    i16 => u16, i16::unsigned_abs, |x| x < 0;
+    i32 => u32, i32::unsigned_abs, |x| x < 0;
+    i64 => u64, i64::unsigned_abs, |x| x < 0;
+    i128 => u128, i128::unsigned_abs, |x| x < 0;
+    isize => usize, isize::unsigned_abs, |x| x < 0;
+}
+
+#[cfg(feature = "ryu")]
+impl Num for f32 {
+    fn push_to<L: LenType, const N: usize>(
+        self,
+        s: &mut String<N, L>,
+    ) -> Result<(), CapacityError> {
+        s.push_str(::ryu::Buffer::new().format(self))
+            .map_err(|_| CapacityError)
+    }
+}
+
+#[cfg(feature = "ryu")]
+impl Num for f64 {
+    fn push_to<L: LenType, const N: usize>(
+        self,
+        s: &mut String<N, L>,
+    ) -> Result<(), CapacityError> {
+        s.push_str(::ryu::Buffer::new().format(self))
+            .map_err(|_| CapacityError)
+    }
+}
+
+/// Creates a [`String`] using interpolation of runtime expressions, like `std::format!`.
+///
+/// Evaluates to `Ok(String<N>)`, or to `Err(core::fmt::Error)` if the formatted text doesn't fit
+/// in `N` bytes or one of the formatting trait implementations fails.
+///
+/// - Give the capacity explicitly, before a semicolon:
+///
+/// ```
+/// use heapless::format;
+///
+/// let x = 42;
+/// let s = format!(16; "x={}", x).unwrap();
+/// assert_eq!(s, "x=42");
+///
+/// assert!(format!(4; "x={}", 1000).is_err());
+/// ```
+///
+/// - Or let it be inferred from the context:
+///
+/// ```
+/// use heapless::{format, String};
+///
+/// let s: String<8> = format!("{:>4}", 7).unwrap();
+/// assert_eq!(s, "   7");
+/// ```
+#[macro_export]
+macro_rules! format {
+    ($n:expr; $($arg:tt)*) => {{
+        let mut s = $crate::String::<{ $n }>::new();
+        match ::core::fmt::Write::write_fmt(&mut s, ::core::format_args!($($arg)*)) {
+            ::core::result::Result::Ok(()) => ::core::result::Result::Ok(s),
+            ::core::result::Result::Err(e) => ::core::result::Result::Err(e),
+        }
+    }};
+    ($($arg:tt)*) => {{
+        let mut s = $crate::String::new();
+        match ::core::fmt::Write::write_fmt(&mut s, ::core::format_args!($($arg)*)) {
+            ::core::result::Result::Ok(()) => ::core::result::Result::Ok(s),
+            ::core::result::Result::Err(e) => ::core::result::Result::Err(e),
+        }
+    }};
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{String, Vec};
+    use super::FromUtf16Error;
+    use crate::{vec::CapacityError, String, Vec};
 
     #[test]
     fn static_new() {
@@ -524,8 +1432,8 @@ mod tests {
         assert!(s.len() == 3);
         assert_eq!(s, "123");
 
-        let e: () = String::<2>::from_str("123").unwrap_err();
-        assert_eq!(e, ());
+        let e: crate::vec::CapacityError = String::<2>::from_str("123").unwrap_err();
+        assert_eq!(e, crate::vec::CapacityError);
     }
 
     #[test]
@@ -604,6 +1512,25 @@ mod tests {
         assert_eq!(s, "he");
     }
 
+    #[test]
+    fn truncate_to_char_boundary() {
+        let mut s: String<8> = String::from("a\u{e9}\u{20ac}");
+        assert_eq!(s.floor_char_boundary(0), 0);
+        assert_eq!(s.floor_char_boundary(2), 1);
+        assert_eq!(s.floor_char_boundary(5), 3);
+        assert_eq!(s.floor_char_boundary(6), 6);
+        assert_eq!(s.floor_char_boundary(7), 6);
+
+        s.truncate_to_char_boundary(7);
+        assert_eq!(s, "a\u{e9}\u{20ac}");
+        s.truncate_to_char_boundary(5);
+        assert_eq!(s, "a\u{e9}");
+        s.truncate_to_char_boundary(2);
+        assert_eq!(s, "a");
+        s.truncate_to_char_boundary(0);
+        assert_eq!(s, "");
+    }
+
     #[test]
     fn pop() {
         let mut s: String<8> = String::from("foo");
@@ -643,4 +1570,305 @@ mod tests {
         assert_eq!(0, s.len());
         assert_eq!(8, s.capacity());
     }
+
+    #[test]
+    fn try_from_iter() {
+        let s: String<8> = String::try_from_iter("h\u{e9}llo".chars()).unwrap();
+        assert_eq!(s, "h\u{e9}llo");
+        assert!(String::<5>::try_from_iter("h\u{e9}llo".chars()).is_err());
+    }
+
+    #[test]
+    fn format() {
+        let s = crate::format!(5; "{}-{}", 12, "ab").unwrap();
+        assert_eq!(s, "12-ab");
+        assert!(crate::format!(4; "{}-{}", 12, "ab").is_err());
+
+        let s: String<4> = crate::format!("abc").unwrap();
+        assert_eq!(s, "abc");
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut s: String<8> = String::from("\u{e9}");
+        s.insert(0, 'a').unwrap();
+        s.insert_str(3, "\u{20ac}").unwrap();
+        s.insert(1, 'b').unwrap();
+        assert_eq!(s, "ab\u{e9}\u{20ac}");
+
+        assert!(s.insert(0, '\u{e9}').is_err());
+        s.insert(0, 'x').unwrap();
+        assert!(s.insert(0, 'x').is_err());
+        assert_eq!(s, "xab\u{e9}\u{20ac}");
+
+        assert_eq!(s.remove(5), '\u{20ac}');
+        assert_eq!(s.remove(3), '\u{e9}');
+        assert_eq!(s.remove(0), 'x');
+        assert_eq!(s, "ab");
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_not_char_boundary() {
+        let mut s: String<8> = String::from("\u{e9}");
+        let _ = s.insert(1, 'a');
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_end() {
+        let mut s: String<8> = String::from("ab");
+        s.remove(2);
+    }
+
+    #[test]
+    fn retain() {
+        let mut s: String<16> = String::from("\u{e9}a\u{20ac}bc\u{e9}");
+        s.retain(|c| c.is_ascii());
+        assert_eq!(s, "abc");
+
+        let mut s: String<16> = String::from("\u{e9}a\u{20ac}bc");
+        let mut seen: String<16> = String::new();
+        s.retain(|c| {
+            seen.push(c).unwrap();
+            c != '\u{20ac}'
+        });
+        assert_eq!(seen, "\u{e9}a\u{20ac}bc");
+        assert_eq!(s, "\u{e9}abc");
+    }
+
+    #[test]
+    fn retain_panic() {
+        let mut s: String<16> = String::from("a\u{e9}b\u{20ac}c");
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            s.retain(|c| match c {
+                'c' => panic!(),
+                c => c.is_ascii(),
+            })
+        }));
+        assert!(res.is_err());
+        assert_eq!(s, "abc");
+    }
+
+    #[test]
+    fn push_num() {
+        macro_rules! check {
+            ($($n:expr),+) => {
+                $(
+                    let s: String<40> = String::from_num($n).unwrap();
+                    assert_eq!(s, std::string::ToString::to_string(&$n).as_str());
+                )+
+            };
+        }
+
+        check!(0u8, 9u8, 10u8, 99u8, 100u8, u8::MAX, i8::MIN, -1i8, i8::MAX);
+        check!(
+            u16::MAX,
+            i16::MIN,
+            1000u16,
+            u32::MAX,
+            i32::MIN,
+            1_000_000i32
+        );
+        check!(u64::MAX, i64::MIN, i64::MAX, 10_000_000_000u64);
+        check!(u128::MAX, i128::MIN, usize::MAX, isize::MIN, 0isize);
+
+        assert_eq!(String::<20>::from_num(i64::MIN).unwrap().len(), 20);
+        assert!(String::<19>::from_num(i64::MIN).is_err());
+
+        let mut s: String<8> = String::from("abc");
+        assert!(s.push_num(123_456u32).is_err());
+        assert_eq!(s, "abc");
+        s.push_num(12_345u32).unwrap();
+        assert_eq!(s, "abc12345");
+    }
+
+    #[cfg(feature = "ryu")]
+    #[test]
+    fn push_float() {
+        let s: String<16> = String::from_num(1.5f32).unwrap();
+        assert_eq!(s, "1.5");
+        let s: String<16> = String::from_num(-0.1f64).unwrap();
+        assert_eq!(s, "-0.1");
+        let s: String<16> = String::from_num(1.0f64).unwrap();
+        assert_eq!(s, "1.0");
+        let s: String<16> = String::from_num(f32::NAN).unwrap();
+        assert_eq!(s, "NaN");
+        let s: String<16> = String::from_num(1e30f64).unwrap();
+        assert_eq!(s, "1e30");
+        assert!(String::<4>::from_num(0.12345f32).is_err());
+    }
+
+    #[test]
+    fn from_utf16() {
+        let v: std::vec::Vec<u16> = "h\u{e9}llo \u{1f600}".encode_utf16().collect();
+        let s: String<16> = String::from_utf16(&v).unwrap();
+        assert_eq!(s, "h\u{e9}llo \u{1f600}");
+        assert_eq!(
+            String::<9>::from_utf16(&v),
+            Err(FromUtf16Error::Capacity(CapacityError))
+        );
+
+        // unpaired surrogate, reported even if the string wouldn't fit
+        assert_eq!(
+            String::<16>::from_utf16(&[0x61, 0xDC00]),
+            Err(FromUtf16Error::InvalidUtf16 { position: 1 })
+        );
+        assert_eq!(
+            String::<1>::from_utf16(&[0x61, 0x62, 0xD800]),
+            Err(FromUtf16Error::InvalidUtf16 { position: 2 })
+        );
+    }
+
+    #[test]
+    fn from_utf8_lossy() {
+        let s: String<16> = String::from_utf8_lossy(b"ab\xFFc\xE2\x82");
+        assert_eq!(s, "ab\u{FFFD}c\u{FFFD}");
+
+        let s: String<16> = String::from_utf8_lossy(b"");
+        assert_eq!(s, "");
+
+        // truncated in the middle of a multi-byte character
+        let s: String<4> = String::from_utf8_lossy("ab\u{e9}\u{e9}".as_bytes());
+        assert_eq!(s, "ab\u{e9}");
+        let s: String<5> = String::from_utf8_lossy(b"ab\xFFc");
+        assert_eq!(s, "ab\u{FFFD}");
+        let s: String<4> = String::from_utf8_lossy(b"ab\xFFc");
+        assert_eq!(s, "ab");
+    }
+
+    #[test]
+    fn trim_in_place() {
+        let mut s: String<16> = String::from("  a b\u{3000}");
+        s.trim_in_place();
+        assert_eq!(s, "a b");
+
+        let mut s: String<16> = String::from("\u{e9}\n");
+        s.trim_in_place();
+        assert_eq!(s, "\u{e9}");
+
+        let mut s: String<16> = String::from("\u{a0} \t");
+        s.trim_in_place();
+        assert_eq!(s, "");
+
+        let mut s: String<16> = String::new();
+        s.trim_in_place();
+        assert_eq!(s, "");
+    }
+
+    #[test]
+    fn split_off() {
+        let mut s: String<8> = String::from("a\u{e9}b\u{e9}");
+        let tail: String<3> = s.split_off(3).unwrap();
+        assert_eq!(s, "a\u{e9}");
+        assert_eq!(tail, "b\u{e9}");
+
+        let tail: String<0> = s.split_off(3).unwrap();
+        assert_eq!(tail, "");
+        assert!(s.split_off::<2>(0).is_err());
+        assert_eq!(s, "a\u{e9}");
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_off_not_char_boundary() {
+        let mut s: String<8> = String::from("a\u{e9}");
+        let _ = s.split_off::<8>(2);
+    }
+
+    #[test]
+    fn replace() {
+        let s: String<16> = String::from("a\u{e9}aa\u{e9}");
+        assert_eq!(s.replace::<16>("\u{e9}", "bc").unwrap(), "abcaabc");
+        assert_eq!(s.replace::<16>("aa", "").unwrap(), "a\u{e9}\u{e9}");
+        assert_eq!(s.replace::<16>("x", "yy").unwrap(), "a\u{e9}aa\u{e9}");
+        assert_eq!(s.replace::<7>("\u{e9}", "bc").unwrap(), "abcaabc");
+        assert!(s.replace::<6>("\u{e9}", "bc").is_err());
+    }
+
+    #[test]
+    fn replace_in_place() {
+        let mut s: String<16> = String::from("aaa\u{e9}aa");
+        s.replace_in_place("aa", "\u{e9}");
+        assert_eq!(s, "\u{e9}a\u{e9}\u{e9}");
+
+        s.replace_in_place("", "");
+        assert_eq!(s, "\u{e9}a\u{e9}\u{e9}");
+    }
+
+    #[test]
+    #[should_panic]
+    fn replace_in_place_different_len() {
+        let mut s: String<16> = String::from("abc");
+        s.replace_in_place("b", "bb");
+    }
+
+    #[test]
+    fn truncating_writer() {
+        use core::fmt::Write;
+
+        let mut s: String<4> = String::from("a");
+        let mut w = s.as_truncating_writer();
+        w.write_str("b\u{e9}").unwrap();
+        assert!(!w.is_truncated());
+        w.write_str("c").unwrap();
+        assert!(w.is_truncated());
+        w.write_str("d").unwrap();
+        assert_eq!(s, "ab\u{e9}");
+
+        let mut s: String<5> = String::new();
+        let (x, y) = ("a\u{e9}", '\u{e9}');
+        write!(s.as_truncating_writer(), "{}{}b", x, y).unwrap();
+        assert_eq!(s, "a\u{e9}\u{e9}");
+
+        // the ellipsis replaces whole characters
+        let mut s: String<5> = String::new();
+        write!(s.as_truncating_writer().with_ellipsis(), "a\u{e9}\u{e9}b").unwrap();
+        assert_eq!(s, "a\u{2026}");
+
+        // no truncation, no ellipsis
+        let mut s: String<5> = String::new();
+        write!(s.as_truncating_writer().with_ellipsis(), "abcde").unwrap();
+        assert_eq!(s, "abcde");
+
+        // the ellipsis doesn't fit, the truncated output is kept
+        let mut s: String<2> = String::new();
+        let mut w = s.as_truncating_writer().with_ellipsis();
+        write!(w, "abc").unwrap();
+        assert!(w.is_truncated());
+        assert_eq!(s, "ab");
+
+        // the original contents are never replaced
+        let mut s: String<6> = String::from("ab");
+        write!(s.as_truncating_writer().with_ellipsis(), "cdefg").unwrap();
+        assert_eq!(s, "abc\u{2026}");
+
+        let mut s: String<5> = String::from("abc");
+        write!(s.as_truncating_writer().with_ellipsis(), "def").unwrap();
+        assert_eq!(s, "abcde");
+    }
+
+    #[test]
+    fn len_type() {
+        let mut s: String<255, u8> = String::new();
+        for _ in 0..255 {
+            s.push('a').unwrap();
+        }
+        assert!(s.push('a').is_err());
+        assert_eq!(s.len(), 255);
+
+        let mut s: String<8, u16> = String::from("a\u{e9}b");
+        s.insert(0, '\u{e9}').unwrap();
+        assert_eq!(s.remove(3), '\u{e9}');
+        let tail = s.split_off::<4>(2).unwrap();
+        assert_eq!(s, "\u{e9}");
+        assert_eq!(tail, "ab");
+        assert_eq!(s, String::<4>::from("\u{e9}"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn len_type_too_small() {
+        let _: String<256, u8> = String::new();
+    }
 }
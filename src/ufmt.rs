@@ -1,20 +1,117 @@
-use crate::{string::String, vec::Vec};
+use crate::{
+    binary_heap::BinaryHeap,
+    sealed::{binary_heap::Kind, vec::LenType},
+    string::String,
+    vec::Vec,
+    HistoryBuffer, IndexMap, IndexSet, LinearMap,
+};
+use hash32::{BuildHasher, Hash};
+use ufmt::{uDebug, uDisplay, Formatter};
 use ufmt_write::uWrite;
 
-impl<const N: usize> uWrite for String<N> {
+impl<L: LenType, const N: usize> uWrite for String<N, L> {
     type Error = ();
     fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
         self.push_str(s)
     }
 }
 
-impl<const N: usize> uWrite for Vec<u8, N> {
+impl<L: LenType, const N: usize> uWrite for Vec<u8, N, L> {
     type Error = ();
     fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
         self.extend_from_slice(s.as_bytes())
     }
 }
 
+// NOTE there's no `uDebug` for `String` because `ufmt` doesn't implement it for `str`
+
+impl<L: LenType, const N: usize> uDisplay for String<N, L> {
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        <str as uDisplay>::fmt(self, f)
+    }
+}
+
+impl<T, L: LenType, const N: usize> uDebug for Vec<T, N, L>
+where
+    T: uDebug,
+{
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        <[T] as uDebug>::fmt(self, f)
+    }
+}
+
+impl<T, K, const N: usize> uDebug for BinaryHeap<T, K, N>
+where
+    K: Kind,
+    T: Ord + uDebug,
+{
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        f.debug_list()?.entries(self.iter())?.finish()
+    }
+}
+
+/// Formats the elements of the buffer as a list, in chronological order.
+impl<T, const N: usize> uDebug for HistoryBuffer<T, N>
+where
+    T: uDebug,
+{
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        f.debug_list()?.entries(self.oldest_ordered())?.finish()
+    }
+}
+
+impl<K, V, S, const N: usize> uDebug for IndexMap<K, V, S, N>
+where
+    K: Eq + Hash + uDebug,
+    V: uDebug,
+    S: BuildHasher,
+{
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        f.debug_map()?.entries(self.iter())?.finish()
+    }
+}
+
+impl<T, S, const N: usize> uDebug for IndexSet<T, S, N>
+where
+    T: Eq + Hash + uDebug,
+    S: BuildHasher,
+{
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        f.debug_set()?.entries(self.iter())?.finish()
+    }
+}
+
+impl<K, V, const N: usize> uDebug for LinearMap<K, V, N>
+where
+    K: Eq + uDebug,
+    V: uDebug,
+{
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        f.debug_map()?.entries(self.iter())?.finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,4 +152,54 @@ mod tests {
 
         assert_eq!(v, b"123 -> Pair { x: 0, y: 1234 }");
     }
+
+    #[test]
+    fn containers() {
+        use crate::{binary_heap::Max, FnvIndexMap, FnvIndexSet};
+
+        let mut s = String::<64>::new();
+
+        let name: String<8> = String::from("ab");
+        uwrite!(s, "{}", name).unwrap();
+        assert_eq!(s, "ab");
+
+        s.clear();
+        let mut v: Vec<Pair, 2> = Vec::new();
+        v.push(Pair { x: 1, y: 2 }).ok().unwrap();
+        uwrite!(s, "{:?}", v).unwrap();
+        assert_eq!(s, "[Pair { x: 1, y: 2 }]");
+
+        s.clear();
+        let mut heap: BinaryHeap<u8, Max, 4> = BinaryHeap::new();
+        heap.push(1).unwrap();
+        uwrite!(s, "{:?}", heap).unwrap();
+        assert_eq!(s, "[1]");
+
+        s.clear();
+        let mut buf: HistoryBuffer<u8, 2> = HistoryBuffer::new();
+        buf.write(1);
+        buf.write(2);
+        buf.write(3);
+        uwrite!(s, "{:?}", buf).unwrap();
+        assert_eq!(s, "[2, 3]");
+
+        s.clear();
+        let mut map: FnvIndexMap<u8, u8, 4> = FnvIndexMap::new();
+        map.insert(1, 2).unwrap();
+        map.insert(3, 4).unwrap();
+        uwrite!(s, "{:?}", map).unwrap();
+        assert_eq!(s, "{1: 2, 3: 4}");
+
+        s.clear();
+        let mut set: FnvIndexSet<u8, 4> = FnvIndexSet::new();
+        set.insert(1).unwrap();
+        uwrite!(s, "{:?}", set).unwrap();
+        assert_eq!(s, "{1}");
+
+        s.clear();
+        let mut map: LinearMap<u8, u8, 4> = LinearMap::new();
+        map.insert(1, 2).unwrap();
+        uwrite!(s, "{:?}", map).unwrap();
+        assert_eq!(s, "{1: 2}");
+    }
 }
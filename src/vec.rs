@@ -1,7 +1,24 @@
-use core::{fmt, hash, iter::FromIterator, mem::MaybeUninit, ops, ptr, slice};
+//! A fixed capacity vector.
+//!
+//! See [`Vec`](type.Vec.html) for details.
+
+use core::{
+    cmp,
+    convert::TryFrom,
+    fmt, hash,
+    iter::{self, FromIterator},
+    marker::PhantomData,
+    mem::{self, MaybeUninit},
+    ops::{self, Bound, RangeBounds},
+    ptr, slice,
+};
 use hash32;
 
-/// A fixed capacity [`Vec`](https://doc.rust-lang.org/std/vec/struct.Vec.html)
+use crate::sealed::vec::VecStorage;
+
+pub use crate::sealed::vec::LenType;
+
+/// A fixed capacity [`Vec`](https://doc.rust-lang.org/std/vec/type.Vec.html)
 ///
 /// # Examples
 ///
@@ -30,12 +47,75 @@ use hash32;
 /// }
 /// assert_eq!(*vec, [7, 1, 2, 3]);
 /// ```
-pub struct Vec<T, const N: usize> {
-    buffer: MaybeUninit<[T; N]>,
-    len: usize,
+///
+/// # Length type
+///
+/// The length of the vector is stored as an `L`, which defaults to `usize`. Small vectors can
+/// store it as a `u8`, `u16` or `u32` instead to save RAM, as long as `N` fits in `L`:
+///
+/// ```
+/// use core::mem::size_of;
+/// use heapless::Vec;
+///
+/// let mut vec: Vec<u8, 16, u8> = Vec::new();
+/// vec.extend_from_slice(b"hello").unwrap();
+/// assert_eq!(vec, b"hello");
+///
+/// assert_eq!(size_of::<Vec<u8, 16, u8>>(), 17);
+/// assert_eq!(size_of::<Vec<u8, 16>>(), 16 + size_of::<usize>());
+/// ```
+///
+/// Creating a vector whose capacity `N` doesn't fit in `L` panics, at compile time when the
+/// vector is created in a `const` context.
+pub type Vec<T, const N: usize, L = usize> = VecInner<T, L, MaybeUninit<[T; N]>>;
+
+/// The implementation shared by [`Vec`](type.Vec.html), [`VecView`](type.VecView.html),
+/// [`SliceVec`](type.SliceVec.html) and [`AlignedVec`](type.AlignedVec.html)
+///
+/// The vectors only differ in the storage `S` of their elements. Use the type aliases instead of
+/// naming this type.
+// NOTE `repr(C)` and the field order are required so that `Vec<T, N, L>` has the same layout as
+// `VecView<T, L>` with `N` elements; see `as_view`
+#[repr(C)]
+pub struct VecInner<T, L: LenType, S: VecStorage<T> + ?Sized> {
+    phantom: PhantomData<T>,
+    len: L,
+    buffer: S,
+}
+
+impl<T, const N: usize> VecStorage<T> for MaybeUninit<[T; N]> {
+    fn as_uninit_slice(&self) -> &[MaybeUninit<T>] {
+        // NOTE(unsafe) `MaybeUninit<[T; N]>` has the same layout as `[MaybeUninit<T>; N]`
+        unsafe { slice::from_raw_parts(self.as_ptr() as *const MaybeUninit<T>, N) }
+    }
+
+    fn as_uninit_mut_slice(&mut self) -> &mut [MaybeUninit<T>] {
+        // NOTE(unsafe) see `as_uninit_slice`
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr() as *mut MaybeUninit<T>, N) }
+    }
+}
+
+impl<T> VecStorage<T> for [MaybeUninit<T>] {
+    fn as_uninit_slice(&self) -> &[MaybeUninit<T>] {
+        self
+    }
+
+    fn as_uninit_mut_slice(&mut self) -> &mut [MaybeUninit<T>] {
+        self
+    }
+}
+
+impl<T> VecStorage<T> for &mut [MaybeUninit<T>] {
+    fn as_uninit_slice(&self) -> &[MaybeUninit<T>] {
+        self
+    }
+
+    fn as_uninit_mut_slice(&mut self) -> &mut [MaybeUninit<T>] {
+        self
+    }
 }
 
-impl<T, const N: usize> Vec<T, N> {
+impl<T, L: LenType, const N: usize> Vec<T, N, L> {
     /// Constructs a new, empty vector with a fixed capacity of `N`
     ///
     /// # Examples
@@ -49,11 +129,33 @@ impl<T, const N: usize> Vec<T, N> {
     /// // allocate the vector in a static variable
     /// static mut X: Vec<u8, 16> = Vec::new();
     /// ```
-    /// `Vec` `const` constructor; wrap the returned value in [`Vec`](../struct.Vec.html)
+    /// `Vec` `const` constructor; wrap the returned value in [`Vec`](../type.Vec.html)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity `N` doesn't fit in the length type `L`.
     pub const fn new() -> Self {
+        assert!(N <= L::MAX, "the capacity doesn't fit in the length type");
+
         Self {
+            phantom: PhantomData,
+            len: L::ZERO,
             buffer: MaybeUninit::uninit(),
-            len: 0,
+        }
+    }
+
+    /// Constructs a vector of length `len` from a buffer, in a `const` context
+    ///
+    /// # Safety
+    ///
+    /// `len` must fit in the length type and be at most `N`, and the first `len` elements of
+    /// `buffer` must be initialized.
+    #[cfg(feature = "c-string")]
+    pub(crate) const unsafe fn from_raw_parts(buffer: MaybeUninit<[T; N]>, len: L) -> Self {
+        Self {
+            phantom: PhantomData,
+            len,
+            buffer,
         }
     }
 
@@ -78,6 +180,33 @@ impl<T, const N: usize> Vec<T, N> {
         Ok(v)
     }
 
+    /// Creates a vector from the elements of an iterator, returning an error instead of
+    /// panicking if they don't fit.
+    ///
+    /// Unlike `collect()`, which panics when the iterator yields more than `N` elements, this
+    /// returns [`CapacityError`](struct.CapacityError.html).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{vec::CapacityError, Vec};
+    ///
+    /// let v: Vec<u8, 4> = Vec::try_from_iter(1..=4).unwrap();
+    /// assert_eq!(v, [1, 2, 3, 4]);
+    ///
+    /// assert_eq!(Vec::<u8, 4>::try_from_iter(1..=5), Err(CapacityError));
+    /// ```
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut vec = Vec::new();
+        for elem in iter {
+            vec.push(elem).map_err(|_| CapacityError)?;
+        }
+        Ok(vec)
+    }
+
     /// Clones a vec into a new vec
     pub(crate) fn clone(&self) -> Self
     where
@@ -88,6 +217,283 @@ impl<T, const N: usize> Vec<T, N> {
         new
     }
 
+    /// Returns the maximum number of elements the vector can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns a view of the vector that erases the capacity `N` from its type.
+    ///
+    /// Functions that take a [`VecView`](type.VecView.html) work with vectors of any capacity
+    /// without being generic over `N`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{vec::VecView, Vec};
+    ///
+    /// fn spare(v: &VecView<u8>) -> usize {
+    ///     v.capacity() - v.len()
+    /// }
+    ///
+    /// let small: Vec<u8, 4> = Vec::from_slice(&[1]).unwrap();
+    /// let large: Vec<u8, 64> = Vec::from_slice(&[1, 2]).unwrap();
+    /// assert_eq!(spare(small.as_view()), 3);
+    /// assert_eq!(spare(large.as_view()), 62);
+    /// ```
+    pub fn as_view(&self) -> &VecView<T, L> {
+        // NOTE(unsafe) `Vec<T, N>` and `VecView<T>` are both `repr(C)` with the same fields, so
+        // a `Vec<T, N>` is a valid `VecView<T>` with a tail of `N` elements
+        unsafe {
+            &*(ptr::slice_from_raw_parts(self as *const Self as *const MaybeUninit<T>, N)
+                as *const VecView<T, L>)
+        }
+    }
+
+    /// Returns a mutable view of the vector that erases the capacity `N` from its type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{vec::VecView, Vec};
+    ///
+    /// fn frame(v: &mut VecView<u8>) {
+    ///     v.push(0x7E).unwrap();
+    /// }
+    ///
+    /// let mut buf: Vec<u8, 16> = Vec::new();
+    /// frame(buf.as_mut_view());
+    /// assert_eq!(buf, [0x7E]);
+    /// ```
+    pub fn as_mut_view(&mut self) -> &mut VecView<T, L> {
+        // NOTE(unsafe) see `as_view`
+        unsafe {
+            &mut *(ptr::slice_from_raw_parts_mut(self as *mut Self as *mut MaybeUninit<T>, N)
+                as *mut VecView<T, L>)
+        }
+    }
+
+    /// Returns the contents of the vector as an array of length `M` if the length
+    /// of the vector is exactly `M`, otherwise returns `Err(self)`.
+    ///
+    /// The elements are moved out of the vector with a single copy of the
+    /// backing storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let buffer: Vec<u8, 42> = Vec::from_slice(&[1, 2, 3, 5, 8]).unwrap();
+    /// let array: [u8; 5] = buffer.into_array().unwrap();
+    /// assert_eq!(array, [1, 2, 3, 5, 8]);
+    ///
+    /// let buffer: Vec<u8, 42> = Vec::from_slice(&[1, 2]).unwrap();
+    /// assert_eq!(buffer.into_array::<3>(), Err(Vec::from_slice(&[1, 2]).unwrap()));
+    /// ```
+    pub fn into_array<const M: usize>(self) -> Result<[T; M], Self> {
+        if self.len() == M {
+            // This is how the unstable `MaybeUninit::array_assume_init` method does it
+            let array = unsafe { (&self.buffer as *const _ as *const [T; M]).read() };
+
+            // We don't want `self`'s destructor to be called because that would drop all the
+            // items in the array
+            mem::forget(self);
+
+            Ok(array)
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Moves the contents of the vector into a vector with capacity `M`, if its
+    /// length is less than or equal to `M`, otherwise returns `Err(self)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let small: Vec<u8, 4> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// let large: Vec<u8, 16> = small.resize_capacity().unwrap();
+    /// assert_eq!(large, [1, 2, 3]);
+    ///
+    /// // Shrinking works as long as the elements fit
+    /// let small: Vec<u8, 3> = large.resize_capacity().unwrap();
+    /// assert_eq!(small, [1, 2, 3]);
+    /// assert!(small.resize_capacity::<2>().is_err());
+    /// ```
+    pub fn resize_capacity<const M: usize>(self) -> Result<Vec<T, M, L>, Self> {
+        let len = self.len();
+        if len > M {
+            return Err(self);
+        }
+
+        let mut other = Vec::new();
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.buffer.as_ptr() as *const T,
+                other.buffer.as_mut_ptr() as *mut T,
+                len,
+            );
+            other.set_len(len);
+        }
+
+        // The elements now belong to `other`
+        mem::forget(self);
+
+        Ok(other)
+    }
+
+    /// Removes the specified range from the vector in bulk, returning all
+    /// removed elements as an iterator. If the iterator is dropped before
+    /// being fully consumed, it drops the remaining removed elements.
+    ///
+    /// The elements after the range are shifted down to close the gap when the
+    /// iterator is dropped. If the iterator is leaked (e.g. with
+    /// `mem::forget`), the vector may have lost and leaked elements
+    /// arbitrarily, including elements outside the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut v: Vec<_, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// let u: Vec<_, 8> = v.drain(1..).collect();
+    /// assert_eq!(v, &[1]);
+    /// assert_eq!(u, &[2, 3]);
+    ///
+    /// // A full range clears the vector, like `clear()` does
+    /// v.drain(..);
+    /// assert_eq!(v, &[]);
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, N, L>
+    where
+        R: RangeBounds<usize>,
+    {
+        let len = self.len();
+        let (start, end) = range_bounds(&range, len);
+
+        // NOTE set the length to the start of the range first, so that the drained and tail
+        // elements are leaked rather than dropped twice if the `Drain` is leaked
+        self.len = L::from_usize(start);
+
+        Drain {
+            vec: self,
+            idx: start,
+            end,
+            tail_start: end,
+            tail_len: len - end,
+        }
+    }
+
+    /// Replaces the specified range in the vector with the elements of the `replace_with`
+    /// iterator, dropping the removed elements.
+    ///
+    /// The elements after the range are shifted once to make room for the new elements, which
+    /// are then written in place.
+    ///
+    /// Returns [`CapacityError`](struct.CapacityError.html), leaving the vector unchanged, if the
+    /// vector can't hold the new elements. The number of new elements is taken from [`ExactSizeIterator::len`]; if the
+    /// iterator yields fewer elements only those are inserted, and any extra elements are left
+    /// in the iterator and dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{vec::CapacityError, Vec};
+    ///
+    /// let mut opts: Vec<u8, 6> = Vec::from_slice(&[1, 2, 3, 4]).unwrap();
+    /// opts.splice(1..3, [7, 8, 9]).unwrap();
+    /// assert_eq!(opts, [1, 7, 8, 9, 4]);
+    ///
+    /// // Doesn't fit
+    /// assert_eq!(opts.splice(..1, [0, 0, 0]), Err(CapacityError));
+    /// assert_eq!(opts, [1, 7, 8, 9, 4]);
+    /// ```
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Result<(), CapacityError>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let len = self.len();
+        let (start, end) = range_bounds(&range, len);
+        let mut iter = replace_with.into_iter();
+        let n = iter.len();
+        if n > N - (len - (end - start)) {
+            return Err(CapacityError);
+        }
+
+        self.drain(start..end);
+        let tail_len = len - end;
+
+        unsafe {
+            // make room for `n` elements; forget the tail while it's moved so that it's leaked
+            // rather than dropped twice if the iterator panics
+            self.len = L::from_usize(start);
+            let base = self.buffer.as_mut_ptr() as *mut T;
+            ptr::copy(base.add(start), base.add(start + n), tail_len);
+
+            let mut written = 0;
+            while written < n {
+                match iter.next() {
+                    Some(item) => {
+                        (self.buffer.as_mut_ptr() as *mut T)
+                            .add(start + written)
+                            .write(item);
+                        written += 1;
+                        self.len = L::from_usize(start + written);
+                    }
+                    None => break,
+                }
+            }
+
+            // close the gap left by a short iterator
+            let base = self.buffer.as_mut_ptr() as *mut T;
+            if written < n {
+                ptr::copy(base.add(start + n), base.add(start + written), tail_len);
+            }
+            self.len = L::from_usize(start + written + tail_len);
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, L: LenType, S: VecStorage<T> + ?Sized> VecInner<T, L, S> {
+    /// Returns the number of elements the storage can hold
+    // NOTE each storage defines a public `capacity`, so that `Vec::capacity` stays a `const fn`
+    fn storage_capacity(&self) -> usize {
+        self.buffer.as_uninit_slice().len()
+    }
+
+    /// Returns a raw pointer to the start of the backing array.
+    ///
+    /// Unlike the pointer of `as_slice`, it can be used to access the whole capacity, e.g. to let
+    /// a peripheral fill the vector before calling [`set_len`](#method.set_len).
+    pub fn as_ptr(&self) -> *const T {
+        self.buffer.as_uninit_slice().as_ptr() as *const T
+    }
+
+    /// Returns a raw mutable pointer to the start of the backing array.
+    ///
+    /// See [`as_ptr`](#method.as_ptr).
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.buffer.as_uninit_mut_slice().as_mut_ptr() as *mut T
+    }
+
     /// Extracts a slice containing the entire vector.
     ///
     /// Equivalent to `&s[..]`.
@@ -102,12 +508,14 @@ impl<T, const N: usize> Vec<T, N> {
     pub fn as_slice(&self) -> &[T] {
         // NOTE(unsafe) avoid bound checks in the slicing operation
         // &buffer[..self.len]
-        unsafe { slice::from_raw_parts(self.buffer.as_ptr() as *const T, self.len) }
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.len.into_usize()) }
     }
 
     /// Extracts a mutable slice containing the entire vector.
     ///
-    /// Equivalent to `&s[..]`.
+    /// Equivalent to `&s[..]`. The elements are stored inline, so there is no `leak` like in
+    /// `std`: the slice of a vector with `'static` storage, e.g. in a `static` or a `StaticCell`,
+    /// is `'static` as well.
     ///
     /// # Examples
     ///
@@ -116,16 +524,19 @@ impl<T, const N: usize> Vec<T, N> {
     /// let mut buffer: Vec<u8, 5> = Vec::from_slice(&[1, 2, 3, 5, 8]).unwrap();
     /// buffer[0] = 9;
     /// assert_eq!(buffer.as_slice(), &[9, 2, 3, 5, 8]);
+    ///
+    /// static mut TABLE: Vec<u16, 8> = Vec::new();
+    ///
+    /// // NOTE(unsafe) `TABLE` is only accessed here
+    /// let table: &'static mut Vec<u16, 8> = unsafe { &mut *core::ptr::addr_of_mut!(TABLE) };
+    /// table.extend_from_slice(&[10, 20, 40]).unwrap();
+    /// let table: &'static mut [u16] = table.as_mut_slice();
+    /// assert_eq!(table, [10, 20, 40]);
     /// ```
-    pub(crate) fn as_mut_slice(&mut self) -> &mut [T] {
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
         // NOTE(unsafe) avoid bound checks in the slicing operation
         // &mut buffer[..self.len]
-        unsafe { slice::from_raw_parts_mut(self.buffer.as_mut_ptr() as *mut T, self.len) }
-    }
-
-    /// Returns the maximum number of elements the vector can hold.
-    pub const fn capacity(&self) -> usize {
-        N
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len.into_usize()) }
     }
 
     /// Clears the vector, removing all values.
@@ -167,7 +578,7 @@ impl<T, const N: usize> Vec<T, N> {
     where
         T: Clone,
     {
-        if self.len + other.len() > self.capacity() {
+        if self.len.into_usize() + other.len() > self.storage_capacity() {
             // won't fit in the `Vec`; don't modify anything and return an error
             Err(())
         } else {
@@ -180,9 +591,134 @@ impl<T, const N: usize> Vec<T, N> {
         }
     }
 
+    /// Copies and appends all elements in a slice to the `Vec`, like
+    /// [`extend_from_slice`](type.Vec.html#method.extend_from_slice).
+    ///
+    /// The capacity is checked once and the elements are appended with a single bulk copy,
+    /// instead of one clone and push per element.
+    ///
+    /// Returns [`CapacityError`](struct.CapacityError.html), leaving the `Vec` unchanged, if the
+    /// elements don't fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{vec::CapacityError, Vec};
+    ///
+    /// let mut rx = Vec::<u8, 8>::new();
+    /// rx.extend_from_copy_slice(&[0xAA, 0x55]).unwrap();
+    /// rx.extend_from_copy_slice(&[1, 2, 3]).unwrap();
+    /// assert_eq!(rx, [0xAA, 0x55, 1, 2, 3]);
+    /// assert_eq!(rx.extend_from_copy_slice(&[0; 4]), Err(CapacityError));
+    /// ```
+    pub fn extend_from_copy_slice(&mut self, other: &[T]) -> Result<(), CapacityError>
+    where
+        T: Copy,
+    {
+        if other.len() > self.storage_capacity() - self.len() {
+            return Err(CapacityError);
+        }
+
+        unsafe { self.extend_from_slice_unchecked(other) };
+        Ok(())
+    }
+
+    /// Copies and appends all elements in a slice to the `Vec` with a single bulk copy, without
+    /// checking the capacity.
+    ///
+    /// # Safety
+    ///
+    /// This assumes the `Vec` has room for all the elements of `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut rx = Vec::<u8, 8>::new();
+    /// let chunk = [1, 2, 3, 4];
+    /// if rx.capacity() - rx.len() >= chunk.len() {
+    ///     unsafe { rx.extend_from_slice_unchecked(&chunk) };
+    /// }
+    /// assert_eq!(rx, [1, 2, 3, 4]);
+    /// ```
+    pub unsafe fn extend_from_slice_unchecked(&mut self, other: &[T])
+    where
+        T: Copy,
+    {
+        let len = self.len.into_usize();
+        debug_assert!(other.len() <= self.storage_capacity() - len);
+
+        ptr::copy_nonoverlapping(other.as_ptr(), self.as_mut_ptr().add(len), other.len());
+        self.len = L::from_usize(len + other.len());
+    }
+
+    /// Clones and appends as many elements of the slice `other` as fit in the `Vec`, and returns
+    /// the number of elements appended.
+    ///
+    /// Unlike [`extend_from_slice`](type.Vec.html#method.extend_from_slice), this never fails;
+    /// the elements that don't fit are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut rx = Vec::<u8, 4>::new();
+    /// assert_eq!(rx.extend_from_slice_truncating(b"ab"), 2);
+    /// assert_eq!(rx.extend_from_slice_truncating(b"cde"), 2);
+    /// assert_eq!(rx, b"abcd");
+    /// assert_eq!(rx.extend_from_slice_truncating(b"f"), 0);
+    /// ```
+    pub fn extend_from_slice_truncating(&mut self, other: &[T]) -> usize
+    where
+        T: Clone,
+    {
+        let n = cmp::min(other.len(), self.storage_capacity() - self.len.into_usize());
+        for elem in &other[..n] {
+            unsafe {
+                self.push_unchecked(elem.clone());
+            }
+        }
+        n
+    }
+
+    /// Appends elements of the iterator until the `Vec` is full, and returns the number of
+    /// elements appended.
+    ///
+    /// No element is taken from the iterator once the `Vec` is full, so the remaining elements
+    /// can still be retrieved from it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec = Vec::<u8, 4>::new();
+    /// let mut iter = 1..=6;
+    /// assert_eq!(vec.extend_truncating(&mut iter), 4);
+    /// assert_eq!(vec, [1, 2, 3, 4]);
+    /// assert_eq!(iter.next(), Some(5));
+    /// ```
+    pub fn extend_truncating<I>(&mut self, iter: I) -> usize
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+        let mut n = 0;
+        while !self.is_full() {
+            match iter.next() {
+                Some(elem) => unsafe { self.push_unchecked(elem) },
+                None => break,
+            }
+            n += 1;
+        }
+        n
+    }
+
     /// Removes the last element from a vector and returns it, or `None` if it's empty
     pub fn pop(&mut self) -> Option<T> {
-        if self.len != 0 {
+        if self.len.into_usize() != 0 {
             Some(unsafe { self.pop_unchecked() })
         } else {
             None
@@ -193,7 +729,7 @@ impl<T, const N: usize> Vec<T, N> {
     ///
     /// Returns back the `item` if the vector is full
     pub fn push(&mut self, item: T) -> Result<(), T> {
-        if self.len < self.capacity() {
+        if self.len.into_usize() < self.storage_capacity() {
             unsafe { self.push_unchecked(item) }
             Ok(())
         } else {
@@ -209,8 +745,9 @@ impl<T, const N: usize> Vec<T, N> {
     pub(crate) unsafe fn pop_unchecked(&mut self) -> T {
         debug_assert!(!self.as_slice().is_empty());
 
-        self.len -= 1;
-        (self.buffer.as_ptr() as *const T).add(self.len).read()
+        let len = self.len.into_usize() - 1;
+        self.len = L::from_usize(len);
+        self.as_ptr().add(len).read()
     }
 
     /// Appends an `item` to the back of the collection
@@ -222,11 +759,9 @@ impl<T, const N: usize> Vec<T, N> {
         // NOTE(ptr::write) the memory slot that we are about to write to is uninitialized. We
         // use `ptr::write` to avoid running `T`'s destructor on the uninitialized memory
         debug_assert!(!self.is_full());
-        (self.buffer.as_mut_ptr() as *mut T)
-            .add(self.len)
-            .write(item);
+        self.as_mut_ptr().add(self.len.into_usize()).write(item);
 
-        self.len += 1;
+        self.len = L::from_usize(self.len.into_usize() + 1);
     }
 
     /// Shortens the vector, keeping the first `len` elements and dropping the rest.
@@ -234,12 +769,12 @@ impl<T, const N: usize> Vec<T, N> {
     pub fn truncate(&mut self, len: usize) {
         unsafe {
             // drop any extra elements
-            while len < self.len {
+            while len < self.len.into_usize() {
                 // decrement len before the drop_in_place(), so a panic on Drop
                 // doesn't re-drop the just-failed value.
-                self.len -= 1;
-                let len = self.len;
-                ptr::drop_in_place(self.as_mut_slice().get_unchecked_mut(len));
+                let len = self.len.into_usize() - 1;
+                self.len = L::from_usize(len);
+                ptr::drop_in_place(self.as_mut_ptr().add(len));
             }
         }
     }
@@ -250,17 +785,17 @@ impl<T, const N: usize> Vec<T, N> {
     /// difference, with each additional slot filled with value. If
     /// new_len is less than len, the Vec is simply truncated.
     ///
-    /// See also [`resize_default`](struct.Vec.html#method.resize_default).
+    /// See also [`resize_default`](type.Vec.html#method.resize_default).
     pub fn resize(&mut self, new_len: usize, value: T) -> Result<(), ()>
     where
         T: Clone,
     {
-        if new_len > self.capacity() {
+        if new_len > self.storage_capacity() {
             return Err(());
         }
 
-        if new_len > self.len {
-            while self.len < new_len {
+        if new_len > self.len.into_usize() {
+            while self.len.into_usize() < new_len {
                 self.push(value.clone()).ok();
             }
         } else {
@@ -273,16 +808,67 @@ impl<T, const N: usize> Vec<T, N> {
     /// Resizes the `Vec` in-place so that `len` is equal to `new_len`.
     ///
     /// If `new_len` is greater than `len`, the `Vec` is extended by the
-    /// difference, with each additional slot filled with `Default::default()`.
-    /// If `new_len` is less than `len`, the `Vec` is simply truncated.
+    /// difference, with each additional slot filled with the result of
+    /// calling the closure `f`. If `new_len` is less than `len`, the `Vec` is
+    /// simply truncated.
     ///
-    /// See also [`resize`](struct.Vec.html#method.resize).
-    pub fn resize_default(&mut self, new_len: usize) -> Result<(), ()>
+    /// Returns [`CapacityError`](struct.CapacityError.html), leaving the `Vec` unchanged, if
+    /// `new_len` is greater than the capacity.
+    ///
+    /// See also [`resize`](type.Vec.html#method.resize).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{vec::CapacityError, Vec};
+    ///
+    /// let mut frame: Vec<u8, 8> = Vec::from_slice(&[0xAA, 0x01]).unwrap();
+    /// let mut n = 0;
+    /// frame
+    ///     .resize_with(5, || {
+    ///         n += 1;
+    ///         n
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(frame, [0xAA, 0x01, 1, 2, 3]);
+    ///
+    /// frame.resize_with(1, || 0).unwrap();
+    /// assert_eq!(frame, [0xAA]);
+    ///
+    /// assert_eq!(frame.resize_with(9, || 0), Err(CapacityError));
+    /// ```
+    pub fn resize_with<F>(&mut self, new_len: usize, mut f: F) -> Result<(), CapacityError>
     where
-        T: Clone + Default,
+        F: FnMut() -> T,
     {
-        self.resize(new_len, T::default())
-    }
+        if new_len > self.storage_capacity() {
+            return Err(CapacityError);
+        }
+
+        if new_len > self.len.into_usize() {
+            while self.len.into_usize() < new_len {
+                unsafe { self.push_unchecked(f()) };
+            }
+        } else {
+            self.truncate(new_len);
+        }
+
+        Ok(())
+    }
+
+    /// Resizes the `Vec` in-place so that `len` is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than `len`, the `Vec` is extended by the
+    /// difference, with each additional slot filled with `Default::default()`.
+    /// If `new_len` is less than `len`, the `Vec` is simply truncated.
+    ///
+    /// See also [`resize`](type.Vec.html#method.resize).
+    pub fn resize_default(&mut self, new_len: usize) -> Result<(), ()>
+    where
+        T: Clone + Default,
+    {
+        self.resize(new_len, T::default())
+    }
 
     /// Forces the length of the vector to `new_len`.
     ///
@@ -374,9 +960,62 @@ impl<T, const N: usize> Vec<T, N> {
     /// Normally, here, one would use [`clear`] instead to correctly drop
     /// the contents and thus not leak memory.
     pub unsafe fn set_len(&mut self, new_len: usize) {
-        debug_assert!(new_len <= self.capacity());
+        debug_assert!(new_len <= self.storage_capacity());
+
+        self.len = L::from_usize(new_len)
+    }
+
+    /// Returns the remaining spare capacity of the vector as a slice of `MaybeUninit<T>`.
+    ///
+    /// The returned slice can be used to fill the vector with data (e.g. by reading from a
+    /// peripheral or DMA) before marking the data as initialized using
+    /// [`assume_init_push_n`](type.Vec.html#method.assume_init_push_n).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut v: Vec<u8, 8> = Vec::from_slice(&[1]).unwrap();
+    ///
+    /// let spare = v.spare_capacity_mut();
+    /// assert_eq!(spare.len(), 7);
+    /// for (i, slot) in spare[..3].iter_mut().enumerate() {
+    ///     slot.write(i as u8 + 2);
+    /// }
+    ///
+    /// // SAFETY: the first 3 elements of the spare capacity were initialized above
+    /// unsafe { v.assume_init_push_n(3) };
+    /// assert_eq!(v, [1, 2, 3, 4]);
+    /// ```
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        let len = self.len.into_usize();
+        &mut self.buffer.as_uninit_mut_slice()[len..]
+    }
+
+    /// Marks the first `n` elements of the spare capacity as initialized, growing the length of
+    /// the vector by `n`.
+    ///
+    /// Unlike [`set_len`](type.Vec.html#method.set_len), the new length is always checked
+    /// against the capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the remaining spare capacity.
+    ///
+    /// # Safety
+    ///
+    /// The first `n` elements of [`spare_capacity_mut`](type.Vec.html#method.spare_capacity_mut)
+    /// must be initialized.
+    pub unsafe fn assume_init_push_n(&mut self, n: usize) {
+        assert!(
+            n <= self.storage_capacity() - self.len.into_usize(),
+            "cannot push {} elements with a spare capacity of {}",
+            n,
+            self.storage_capacity() - self.len.into_usize()
+        );
 
-        self.len = new_len
+        self.len = L::from_usize(self.len.into_usize() + n);
     }
 
     /// Removes an element from the vector and returns it.
@@ -408,7 +1047,7 @@ impl<T, const N: usize> Vec<T, N> {
     /// assert_eq!(&*v, ["baz", "qux"]);
     /// ```
     pub fn swap_remove(&mut self, index: usize) -> T {
-        assert!(index < self.len);
+        assert!(index < self.len.into_usize());
         unsafe { self.swap_remove_unchecked(index) }
     }
 
@@ -452,7 +1091,23 @@ impl<T, const N: usize> Vec<T, N> {
     /// Returns true if the vec is full
     #[inline]
     pub fn is_full(&self) -> bool {
-        self.len == self.capacity()
+        self.len.into_usize() == self.storage_capacity()
+    }
+
+    /// Returns the number of elements that can still be pushed into the vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<u8, 4> = Vec::new();
+    /// vec.push(1).unwrap();
+    /// assert_eq!(vec.remaining_capacity(), 3);
+    /// ```
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        self.storage_capacity() - self.len.into_usize()
     }
 
     /// Returns `true` if `needle` is a prefix of the Vec.
@@ -475,7 +1130,7 @@ impl<T, const N: usize> Vec<T, N> {
         T: PartialEq,
     {
         let n = needle.len();
-        self.len >= n && needle == &self[..n]
+        self.len.into_usize() >= n && needle == &self[..n]
     }
 
     /// Returns `true` if `needle` is a suffix of the Vec.
@@ -500,313 +1155,1169 @@ impl<T, const N: usize> Vec<T, N> {
         let (v, n) = (self.len(), needle.len());
         v >= n && needle == &self[v - n..]
     }
-}
-
-// Trait implementations
-
-impl<T, const N: usize> Default for Vec<T, N> {
-    fn default() -> Self {
-        Self::new()
-    }
-}
 
-impl<T, const N: usize> fmt::Debug for Vec<T, N>
-where
-    T: fmt::Debug,
-{
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        <[T] as fmt::Debug>::fmt(self, f)
-    }
-}
+    /// Inserts an element at position `index` within the vector, shifting all
+    /// elements after it to the right.
+    ///
+    /// Returns back the `element` if the vector is full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<u8, 5> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// vec.insert(1, 4).unwrap();
+    /// assert_eq!(vec, [1, 4, 2, 3]);
+    /// vec.insert(4, 5).unwrap();
+    /// assert_eq!(vec, [1, 4, 2, 3, 5]);
+    /// assert_eq!(vec.insert(0, 6), Err(6));
+    /// ```
+    pub fn insert(&mut self, index: usize, element: T) -> Result<(), T> {
+        let len = self.len();
+        if index > len {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                index, len
+            );
+        }
 
-impl<const N: usize> fmt::Write for Vec<u8, N> {
-    fn write_str(&mut self, s: &str) -> fmt::Result {
-        match self.extend_from_slice(s.as_bytes()) {
-            Ok(()) => Ok(()),
-            Err(_) => Err(fmt::Error),
+        // check there's space for the new element
+        if self.is_full() {
+            return Err(element);
         }
-    }
-}
 
-// PER: Please check if non drop types are correctly optimized
-impl<T, const N: usize> Drop for Vec<T, N> {
-    fn drop(&mut self) {
-        // We drop each element used in the vector by turning into a &mut[T]
         unsafe {
-            ptr::drop_in_place(self.as_mut_slice());
+            // infallible
+            // The spot to put the new value
+            {
+                let p = self.as_mut_ptr().add(index);
+                // Shift everything over to make space. (Duplicating the
+                // `index`th element into two consecutive places.)
+                ptr::copy(p, p.offset(1), len - index);
+                // Write it in, overwriting the first copy of the `index`th
+                // element.
+                ptr::write(p, element);
+            }
+            self.set_len(len + 1);
         }
+
+        Ok(())
     }
-}
 
-impl<T, const N: usize> Extend<T> for Vec<T, N> {
-    fn extend<I>(&mut self, iter: I)
+    /// Inserts an element into a sorted vector, keeping it sorted, and returns the index it was
+    /// inserted at.
+    ///
+    /// The insertion point is found with a binary search. The element is inserted after any
+    /// elements equal to it, so elements that compare equal keep their insertion order. If the
+    /// vector is not sorted, the insertion point is unspecified.
+    ///
+    /// Returns back the `element` if the vector is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<u8, 4> = Vec::from_slice(&[1, 3, 5]).unwrap();
+    /// assert_eq!(vec.insert_sorted(4), Ok(2));
+    /// assert_eq!(vec, [1, 3, 4, 5]);
+    /// assert_eq!(vec.insert_sorted(2), Err(2));
+    /// ```
+    pub fn insert_sorted(&mut self, element: T) -> Result<usize, T>
     where
-        I: IntoIterator<Item = T>,
+        T: Ord,
     {
-        self.extend(iter)
+        let index = self.partition_point(|x| x <= &element);
+        self.insert(index, element).map(|_| index)
     }
-}
 
-impl<'a, T, const N: usize> Extend<&'a T> for Vec<T, N>
-where
-    T: 'a + Copy,
-{
-    fn extend<I>(&mut self, iter: I)
+    /// Inserts an element into a vector sorted by the key extraction function `f`, keeping it
+    /// sorted, and returns the index it was inserted at.
+    ///
+    /// See [`insert_sorted`](type.Vec.html#method.insert_sorted) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// // (deadline, timer id)
+    /// let mut timers: Vec<(u32, u8), 8> = Vec::new();
+    /// timers.insert_sorted_by_key((300, 0), |t| t.0).unwrap();
+    /// timers.insert_sorted_by_key((100, 1), |t| t.0).unwrap();
+    /// timers.insert_sorted_by_key((300, 2), |t| t.0).unwrap();
+    /// timers.insert_sorted_by_key((200, 3), |t| t.0).unwrap();
+    /// assert_eq!(timers, [(100, 1), (200, 3), (300, 0), (300, 2)]);
+    /// ```
+    pub fn insert_sorted_by_key<K, F>(&mut self, element: T, mut f: F) -> Result<usize, T>
     where
-        I: IntoIterator<Item = &'a T>,
+        F: FnMut(&T) -> K,
+        K: Ord,
     {
-        self.extend(iter.into_iter().cloned())
+        let key = f(&element);
+        let index = self.partition_point(|x| f(x) <= key);
+        self.insert(index, element).map(|_| index)
     }
-}
 
-impl<T, const N: usize> hash::Hash for Vec<T, N>
-where
-    T: core::hash::Hash,
-{
-    fn hash<H: hash::Hasher>(&self, state: &mut H) {
-        <[T] as hash::Hash>::hash(self, state)
+    /// Removes and returns the element at position `index` within the vector,
+    /// shifting all elements after it to the left.
+    ///
+    /// Note: Because this shifts over the remaining elements, it has a
+    /// worst-case performance of *O*(n). If you don't need the order of
+    /// elements to be preserved, use [`swap_remove`] instead.
+    ///
+    /// [`swap_remove`]: Vec::swap_remove
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+    /// assert_eq!(v.remove(1), 2);
+    /// assert_eq!(v, [1, 3]);
+    /// ```
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        if index >= len {
+            panic!("removal index (is {}) should be < len (is {})", index, len);
+        }
+        unsafe {
+            // infallible
+            let ret;
+            {
+                // the place we are taking from.
+                let ptr = self.as_mut_ptr().add(index);
+                // copy it out, unsafely having a copy of the value on
+                // the stack and in the vector at the same time.
+                ret = ptr::read(ptr);
+
+                // Shift everything down to fill in that spot.
+                ptr::copy(ptr.offset(1), ptr, len - index - 1);
+            }
+            self.set_len(len - 1);
+            ret
+        }
     }
-}
 
-impl<T, const N: usize> hash32::Hash for Vec<T, N>
-where
-    T: hash32::Hash,
-{
-    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
-        <[T] as hash32::Hash>::hash(self, state)
-    }
-}
+    /// Splits the vector into two at the given index, moving the elements `[at, len)` into a
+    /// newly allocated vector of capacity `M`. `self` is left containing the elements `[0, at)`.
+    ///
+    /// Returns [`CapacityError`](struct.CapacityError.html), leaving `self` unchanged, if the
+    /// tail doesn't fit in capacity `M`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::{vec::CapacityError, Vec};
+    ///
+    /// let mut vec: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3, 4, 5]).unwrap();
+    /// let tail: Vec<u8, 4> = vec.split_off(2).unwrap();
+    /// assert_eq!(vec, [1, 2]);
+    /// assert_eq!(tail, [3, 4, 5]);
+    ///
+    /// // The tail doesn't fit
+    /// assert_eq!(vec.split_off::<1>(0), Err(CapacityError));
+    /// assert_eq!(vec, [1, 2]);
+    /// ```
+    pub fn split_off<const M: usize>(&mut self, at: usize) -> Result<Vec<T, M, L>, CapacityError> {
+        let len = self.len();
+        if at > len {
+            panic!("`at` split index (is {}) should be <= len (is {})", at, len);
+        }
 
-impl<'a, T, const N: usize> IntoIterator for &'a Vec<T, N> {
-    type Item = &'a T;
-    type IntoIter = slice::Iter<'a, T>;
+        let other_len = len - at;
+        if other_len > M {
+            return Err(CapacityError);
+        }
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+        let mut other = Vec::new();
+        unsafe {
+            self.set_len(at);
+            ptr::copy_nonoverlapping(
+                self.as_ptr().add(at),
+                other.buffer.as_mut_ptr() as *mut T,
+                other_len,
+            );
+            other.set_len(other_len);
+        }
+        Ok(other)
     }
-}
 
-impl<'a, T, const N: usize> IntoIterator for &'a mut Vec<T, N> {
-    type Item = &'a mut T;
-    type IntoIter = slice::IterMut<'a, T>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter_mut()
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all elements `e` for which `f(&e)` returns `false`.
+    /// This method operates in place, visiting each element exactly once in the
+    /// original order, and preserves the order of the retained elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 2, 3, 4]).unwrap();
+    /// vec.retain(|&x| x % 2 == 0);
+    /// assert_eq!(vec, [2, 4]);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|elem| f(elem));
     }
-}
 
-impl<T, const N: usize> FromIterator<T> for Vec<T, N> {
-    fn from_iter<I>(iter: I) -> Self
+    /// Retains only the elements specified by the predicate, passing a mutable reference to it.
+    ///
+    /// In other words, remove all elements `e` such that `f(&mut e)` returns `false`.
+    /// This method operates in place, visiting each element exactly once in the
+    /// original order, and preserves the order of the retained elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&[1, 2, 3, 4]).unwrap();
+    /// vec.retain_mut(|x| if *x <= 3 {
+    ///     *x += 1;
+    ///     true
+    /// } else {
+    ///     false
+    /// });
+    /// assert_eq!(vec, [2, 3, 4]);
+    /// ```
+    pub fn retain_mut<F>(&mut self, mut f: F)
     where
-        I: IntoIterator<Item = T>,
+        F: FnMut(&mut T) -> bool,
     {
-        let mut vec = Vec::new();
-        for i in iter {
-            vec.push(i).ok().expect("Vec::from_iter overflow");
+        let original_len = self.len();
+        // Avoid double drop if the drop guard is not executed,
+        // since we may make some holes during the process.
+        unsafe { self.set_len(0) };
+
+        // Vec: [Kept, Kept, Hole, Hole, Hole, Hole, Unchecked, Unchecked]
+        //      |<-              processed len   ->| ^- next to check
+        //                  |<-  deleted cnt     ->|
+        //      |<-              original_len                          ->|
+        // Kept: Elements which predicate returns true on.
+        // Hole: Moved or dropped element slot.
+        // Unchecked: Unchecked valid elements.
+        //
+        // This drop guard will be invoked when predicate or `drop` of element panicked.
+        // It shifts unchecked elements to cover holes and `set_len` to the correct length.
+        // In cases when predicate and `drop` never panic, it will be optimized out.
+        struct BackshiftOnDrop<'a, T, L: LenType, S: VecStorage<T> + ?Sized> {
+            v: &'a mut VecInner<T, L, S>,
+            processed_len: usize,
+            deleted_cnt: usize,
+            original_len: usize,
         }
-        vec
-    }
-}
 
-/// An iterator that moves out of an [`Vec`][`Vec`].
-///
-/// This struct is created by calling the `into_iter` method on [`Vec`][`Vec`].
-///
-/// [`Vec`]: (https://doc.rust-lang.org/std/vec/struct.Vec.html)
-///
-pub struct IntoIter<T, const N: usize> {
-    vec: Vec<T, N>,
-    next: usize,
-}
-
-impl<T, const N: usize> Iterator for IntoIter<T, N> {
-    type Item = T;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.next < self.vec.len() {
-            let item = unsafe { (self.vec.buffer.as_ptr() as *const T).add(self.next).read() };
-            self.next += 1;
-            Some(item)
-        } else {
-            None
+        impl<T, L: LenType, S: VecStorage<T> + ?Sized> Drop for BackshiftOnDrop<'_, T, L, S> {
+            fn drop(&mut self) {
+                if self.deleted_cnt > 0 {
+                    // SAFETY: Trailing unchecked items must be valid since we never touch them.
+                    unsafe {
+                        let base = self.v.as_mut_ptr();
+                        ptr::copy(
+                            base.add(self.processed_len),
+                            base.add(self.processed_len - self.deleted_cnt),
+                            self.original_len - self.processed_len,
+                        );
+                    }
+                }
+                // SAFETY: After filling holes, all items are in contiguous memory.
+                unsafe {
+                    self.v.set_len(self.original_len - self.deleted_cnt);
+                }
+            }
         }
-    }
-}
 
-impl<T, const N: usize> Clone for IntoIter<T, N>
-where
-    T: Clone,
-{
-    fn clone(&self) -> Self {
-        let mut vec = Vec::new();
+        let mut g = BackshiftOnDrop {
+            v: self,
+            processed_len: 0,
+            deleted_cnt: 0,
+            original_len,
+        };
 
-        if self.next < self.vec.len() {
-            let s = unsafe {
-                slice::from_raw_parts(
-                    (self.vec.buffer.as_ptr() as *const T).add(self.next),
-                    self.vec.len() - self.next,
-                )
-            };
-            vec.extend_from_slice(s).ok();
+        while g.processed_len != original_len {
+            let base = g.v.as_mut_ptr();
+            // SAFETY: Unchecked element must be valid.
+            let cur = unsafe { &mut *base.add(g.processed_len) };
+            if !f(cur) {
+                // Advance early to avoid double drop if `drop_in_place` panicked.
+                g.processed_len += 1;
+                g.deleted_cnt += 1;
+                // SAFETY: We never touch this element again after dropped.
+                unsafe { ptr::drop_in_place(cur) };
+            } else if g.deleted_cnt > 0 {
+                // SAFETY: `deleted_cnt` > 0, so the hole slot must not overlap with current
+                // element. We use copy for move, and never touch this element again.
+                unsafe {
+                    let hole_slot = base.add(g.processed_len - g.deleted_cnt);
+                    ptr::copy_nonoverlapping(cur, hole_slot, 1);
+                }
+                g.processed_len += 1;
+            } else {
+                g.processed_len += 1;
+            }
         }
 
-        Self { vec, next: 0 }
+        // All item are processed. This can be optimized to `set_len` by LLVM.
+        drop(g);
     }
-}
 
-// PER: is this correct
-impl<T, const N: usize> Drop for IntoIter<T, N> {
-    fn drop(&mut self) {
-        unsafe {
-            // Drop all the elements that have not been moved out of vec
-            ptr::drop_in_place(&mut self.vec.as_mut_slice()[self.next..]);
-            // Prevent dropping of other elements
-            self.vec.len = 0;
+    /// Removes all but the first of consecutive elements in the vector that resolve to the same
+    /// key.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<i32, 8> = Vec::from_slice(&[10, 20, 21, 30, 20]).unwrap();
+    /// vec.dedup_by_key(|i| *i / 10);
+    /// assert_eq!(vec, [10, 20, 30, 20]);
+    /// ```
+    #[inline]
+    pub fn dedup_by_key<F, K>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    /// Removes all but the first of consecutive elements in the vector satisfying a given
+    /// equality relation.
+    ///
+    /// The `same_bucket` function is passed references to two elements from the vector and
+    /// must determine if the elements compare equal. The elements are passed in opposite order
+    /// from their order in the vector, so if `same_bucket(a, b)` returns `true`, `a` is removed.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&["foo", "bar", "Bar", "baz", "bar"]).unwrap();
+    /// vec.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    /// assert_eq!(vec, ["foo", "bar", "baz", "bar"]);
+    /// ```
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let len = self.len();
+        if len <= 1 {
+            return;
+        }
+
+        // This drop guard will be invoked when `same_bucket` or `drop` of element panicked.
+        // It shifts the unread elements to cover the gap and `set_len` to the correct length.
+        struct FillGapOnDrop<'a, T, L: LenType, S: VecStorage<T> + ?Sized> {
+            // Offset of the element we want to check if it is duplicate
+            read: usize,
+            // Offset of the place where we want to place the non-duplicate
+            // when we find it.
+            write: usize,
+            v: &'a mut VecInner<T, L, S>,
+        }
+
+        impl<T, L: LenType, S: VecStorage<T> + ?Sized> Drop for FillGapOnDrop<'_, T, L, S> {
+            fn drop(&mut self) {
+                // SAFETY: `read..len` are unread valid elements and `write <= read`.
+                unsafe {
+                    let base = self.v.as_mut_ptr();
+                    let len = self.v.len();
+                    ptr::copy(base.add(self.read), base.add(self.write), len - self.read);
+                    self.v.set_len(len - (self.read - self.write));
+                }
+            }
         }
+
+        // The element at index 0 is never a duplicate
+        let mut gap = FillGapOnDrop {
+            read: 1,
+            write: 1,
+            v: self,
+        };
+
+        while gap.read < len {
+            let base = gap.v.as_mut_ptr();
+            // SAFETY: `read` is an unread valid element and `write - 1` is the last kept element.
+            unsafe {
+                let read_ptr = base.add(gap.read);
+                let prev_ptr = base.add(gap.write - 1);
+
+                if same_bucket(&mut *read_ptr, &mut *prev_ptr) {
+                    // Advance early to avoid double drop if `drop_in_place` panicked.
+                    gap.read += 1;
+                    ptr::drop_in_place(read_ptr);
+                } else {
+                    // `read` and `write` may be equal if nothing was removed yet.
+                    ptr::copy(read_ptr, base.add(gap.write), 1);
+                    gap.write += 1;
+                    gap.read += 1;
+                }
+            }
+        }
+
+        // All elements are processed, so there's nothing left to shift down
+        unsafe { gap.v.set_len(gap.write) };
+        mem::forget(gap);
+    }
+
+    /// Removes consecutive repeated elements in the vector according to the
+    /// [`PartialEq`] trait implementation.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<u8, 8> = Vec::from_slice(&[1, 2, 2, 3, 2]).unwrap();
+    /// vec.dedup();
+    /// assert_eq!(vec, [1, 2, 3, 2]);
+    /// ```
+    #[inline]
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b)
     }
 }
 
-impl<T, const N: usize> IntoIterator for Vec<T, N> {
-    type Item = T;
-    type IntoIter = IntoIter<T, N>;
+/// The error returned when the elements of an iterator don't fit in a fixed capacity container,
+/// e.g. by [`Vec::try_from_iter`](type.Vec.html#method.try_from_iter).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CapacityError;
 
-    fn into_iter(self) -> Self::IntoIter {
-        IntoIter { vec: self, next: 0 }
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("insufficient capacity")
     }
 }
 
-impl<A, B, const N1: usize, const N2: usize> PartialEq<Vec<B, N2>> for Vec<A, N1>
+/// Converts `range` into `start..end` indices of a vector of length `len`
+///
+/// Panics if the range is decreasing or out of bounds
+fn range_bounds<R>(range: &R, len: usize) -> (usize, usize)
 where
-    A: PartialEq<B>,
+    R: RangeBounds<usize>,
 {
-    fn eq(&self, other: &Vec<B, N2>) -> bool {
-        <[A]>::eq(self, &**other)
+    let start = match range.start_bound() {
+        Bound::Included(&n) => n,
+        Bound::Excluded(&n) => n.checked_add(1).expect("start index overflow"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&n) => n.checked_add(1).expect("end index overflow"),
+        Bound::Excluded(&n) => n,
+        Bound::Unbounded => len,
+    };
+    assert!(start <= end, "range start is greater than range end");
+    assert!(end <= len, "range end is out of bounds");
+    (start, end)
+}
+
+// Trait implementations
+
+impl<T, L: LenType, const N: usize> Default for Vec<T, N, L> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-// Vec<A, N> == [B]
-impl<A, B, const N: usize> PartialEq<[B]> for Vec<A, N>
+impl<T, L: LenType, S: VecStorage<T> + ?Sized> fmt::Debug for VecInner<T, L, S>
 where
-    A: PartialEq<B>,
+    T: fmt::Debug,
 {
-    fn eq(&self, other: &[B]) -> bool {
-        <[A]>::eq(self, &other[..])
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <[T] as fmt::Debug>::fmt(self, f)
     }
 }
 
-// Vec<A, N> == &[B]
-impl<A, B, const N: usize> PartialEq<&[B]> for Vec<A, N>
+/// Appends the UTF-8 bytes of the formatted text to the `Vec`.
+///
+/// Writing fails with `fmt::Error` once a string doesn't fit in the remaining capacity. A string
+/// that doesn't fit is not appended at all, but the text written by the earlier `write_str` calls
+/// of the same `write!` is kept.
+///
+/// # Examples
+///
+/// ```
+/// use core::fmt::Write;
+/// use heapless::Vec;
+///
+/// let mut buf: Vec<u8, 8> = Vec::new();
+/// write!(buf, "T={}", 21).unwrap();
+/// assert_eq!(buf, b"T=21");
+///
+/// assert!(write!(buf, ",H={}", 50).is_err());
+/// ```
+impl<L: LenType, S: VecStorage<u8> + ?Sized> fmt::Write for VecInner<u8, L, S> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match self.extend_from_slice(s.as_bytes()) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+/// Clones the elements of a slice into a `Vec`, returning an error if they don't fit.
+///
+/// ```
+/// use core::convert::TryFrom;
+/// use heapless::{vec::CapacityError, Vec};
+///
+/// let v = Vec::<u8, 4>::try_from(&b"abc"[..]).unwrap();
+/// assert_eq!(v, b"abc");
+///
+/// assert_eq!(Vec::<u8, 2>::try_from(&b"abc"[..]), Err(CapacityError));
+/// ```
+impl<'a, T, L: LenType, const N: usize> TryFrom<&'a [T]> for Vec<T, N, L>
 where
-    A: PartialEq<B>,
+    T: Clone,
 {
-    fn eq(&self, other: &&[B]) -> bool {
-        <[A]>::eq(self, &other[..])
+    type Error = CapacityError;
+
+    fn try_from(slice: &'a [T]) -> Result<Self, CapacityError> {
+        Vec::from_slice(slice).map_err(|_| CapacityError)
     }
 }
 
-// Vec<A, N> == &mut [B]
-impl<A, B, const N: usize> PartialEq<&mut [B]> for Vec<A, N>
+impl<T, L: LenType, const N: usize> TryFrom<Vec<T, N, L>> for [T; N] {
+    type Error = Vec<T, N, L>;
+
+    /// Converts a full `Vec` into an array, returning the `Vec` back if it is not full.
+    fn try_from(vec: Vec<T, N, L>) -> Result<[T; N], Vec<T, N, L>> {
+        vec.into_array()
+    }
+}
+
+// PER: Please check if non drop types are correctly optimized
+impl<T, L: LenType, S: VecStorage<T> + ?Sized> Drop for VecInner<T, L, S> {
+    fn drop(&mut self) {
+        // We drop each element used in the vector by turning into a &mut[T]
+        unsafe {
+            ptr::drop_in_place(self.as_mut_slice());
+        }
+    }
+}
+
+impl<T, L: LenType, S: VecStorage<T> + ?Sized> Extend<T> for VecInner<T, L, S> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.extend(iter)
+    }
+}
+
+impl<'a, T, L: LenType, S: VecStorage<T> + ?Sized> Extend<&'a T> for VecInner<T, L, S>
 where
-    A: PartialEq<B>,
+    T: 'a + Copy,
 {
-    fn eq(&self, other: &&mut [B]) -> bool {
-        <[A]>::eq(self, &other[..])
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().cloned())
     }
 }
 
-// Vec<A, N> == [B; M]
-// Equality does not require equal capacity
-impl<A, B, const N: usize, const M: usize> PartialEq<[B; M]> for Vec<A, N>
+impl<T, L: LenType, S: VecStorage<T> + ?Sized> hash::Hash for VecInner<T, L, S>
 where
-    A: PartialEq<B>,
+    T: core::hash::Hash,
 {
-    fn eq(&self, other: &[B; M]) -> bool {
-        <[A]>::eq(self, &other[..])
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        <[T] as hash::Hash>::hash(self, state)
     }
 }
 
-// Vec<A, N> == &[B; M]
-// Equality does not require equal capacity
-impl<A, B, const N: usize, const M: usize> PartialEq<&[B; M]> for Vec<A, N>
+impl<T, L: LenType, S: VecStorage<T> + ?Sized> hash32::Hash for VecInner<T, L, S>
 where
-    A: PartialEq<B>,
+    T: hash32::Hash,
 {
-    fn eq(&self, other: &&[B; M]) -> bool {
-        <[A]>::eq(self, &other[..])
+    fn hash<H: hash32::Hasher>(&self, state: &mut H) {
+        <[T] as hash32::Hash>::hash(self, state)
     }
 }
 
-// Implements Eq if underlying data is Eq
-impl<T, const N: usize> Eq for Vec<T, N> where T: Eq {}
-
-impl<T, const N: usize> ops::Deref for Vec<T, N> {
-    type Target = [T];
+impl<'a, T, L: LenType, S: VecStorage<T> + ?Sized> IntoIterator for &'a VecInner<T, L, S> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
 
-    fn deref(&self) -> &[T] {
-        self.as_slice()
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
-impl<T, const N: usize> ops::DerefMut for Vec<T, N> {
-    fn deref_mut(&mut self) -> &mut [T] {
-        self.as_mut_slice()
-    }
-}
+impl<'a, T, L: LenType, S: VecStorage<T> + ?Sized> IntoIterator for &'a mut VecInner<T, L, S> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
 
-impl<T, const N: usize> AsRef<Vec<T, N>> for Vec<T, N> {
-    #[inline]
-    fn as_ref(&self) -> &Self {
-        self
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
     }
 }
 
-impl<T, const N: usize> AsMut<Vec<T, N>> for Vec<T, N> {
-    #[inline]
-    fn as_mut(&mut self) -> &mut Self {
-        self
+impl<T, L: LenType, const N: usize> FromIterator<T> for Vec<T, N, L> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut vec = Vec::new();
+        for i in iter {
+            vec.push(i).ok().expect("Vec::from_iter overflow");
+        }
+        vec
     }
 }
 
-impl<T, const N: usize> AsRef<[T]> for Vec<T, N> {
-    #[inline]
-    fn as_ref(&self) -> &[T] {
-        self
-    }
+/// An iterator that moves out of an [`Vec`][`Vec`].
+///
+/// This struct is created by calling the `into_iter` method on [`Vec`][`Vec`].
+///
+/// [`Vec`]: (https://doc.rust-lang.org/std/vec/type.Vec.html)
+///
+pub struct IntoIter<T, const N: usize, L: LenType = usize> {
+    vec: Vec<T, N, L>,
+    next: usize,
 }
 
-impl<T, const N: usize> AsMut<[T]> for Vec<T, N> {
-    #[inline]
-    fn as_mut(&mut self) -> &mut [T] {
-        self
+impl<T, L: LenType, const N: usize> Iterator for IntoIter<T, N, L> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next < self.vec.len() {
+            let item = unsafe { (self.vec.buffer.as_ptr() as *const T).add(self.next).read() };
+            self.next += 1;
+            Some(item)
+        } else {
+            None
+        }
     }
 }
 
-impl<T, const N: usize> Clone for Vec<T, N>
+impl<T, L: LenType, const N: usize> Clone for IntoIter<T, N, L>
 where
     T: Clone,
 {
     fn clone(&self) -> Self {
-        self.clone()
+        let mut vec = Vec::new();
+
+        if self.next < self.vec.len() {
+            let s = unsafe {
+                slice::from_raw_parts(
+                    (self.vec.buffer.as_ptr() as *const T).add(self.next),
+                    self.vec.len() - self.next,
+                )
+            };
+            vec.extend_from_slice(s).ok();
+        }
+
+        Self { vec, next: 0 }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Vec;
-    use core::fmt::Write;
+// PER: is this correct
+impl<T, L: LenType, const N: usize> Drop for IntoIter<T, N, L> {
+    fn drop(&mut self) {
+        unsafe {
+            // Drop all the elements that have not been moved out of vec
+            ptr::drop_in_place(&mut self.vec.as_mut_slice()[self.next..]);
+            // Prevent dropping of other elements
+            self.vec.len = L::ZERO;
+        }
+    }
+}
 
-    #[test]
-    fn static_new() {
-        static mut _V: Vec<i32, 4> = Vec::new();
+impl<T, L: LenType, const N: usize> IntoIterator for Vec<T, N, L> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N, L>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { vec: self, next: 0 }
     }
+}
 
-    #[test]
-    fn stack_new() {
-        static mut _V: Vec<i32, 4> = Vec::new();
+/// A draining iterator for [`Vec`](type.Vec.html).
+///
+/// This `struct` is created by [`Vec::drain`](type.Vec.html#method.drain).
+/// See its documentation for more.
+pub struct Drain<'a, T, const N: usize, L: LenType = usize> {
+    vec: &'a mut Vec<T, N, L>,
+    // index of the next element yielded from the front
+    idx: usize,
+    // index after the next element yielded from the back
+    end: usize,
+    // index and length of the elements after the drained range
+    tail_start: usize,
+    tail_len: usize,
+}
+
+impl<T, L: LenType, const N: usize> Drain<'_, T, N, L> {
+    /// Returns the remaining items of this iterator as a slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use heapless::Vec;
+    ///
+    /// let mut vec: Vec<_, 8> = Vec::from_slice(&['a', 'b', 'c']).unwrap();
+    /// let mut drain = vec.drain(..);
+    /// assert_eq!(drain.as_slice(), &['a', 'b', 'c']);
+    /// let _ = drain.next().unwrap();
+    /// assert_eq!(drain.as_slice(), &['b', 'c']);
+    /// ```
+    pub fn as_slice(&self) -> &[T] {
+        unsafe {
+            slice::from_raw_parts(
+                (self.vec.buffer.as_ptr() as *const T).add(self.idx),
+                self.end - self.idx,
+            )
+        }
     }
+}
 
-    macro_rules! droppable {
-        () => {
-            struct Droppable;
+impl<T, L: LenType, const N: usize> Iterator for Drain<'_, T, N, L> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.idx == self.end {
+            return None;
+        }
+
+        let item = unsafe { (self.vec.buffer.as_ptr() as *const T).add(self.idx).read() };
+        self.idx += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
+    }
+}
+
+impl<T, L: LenType, const N: usize> DoubleEndedIterator for Drain<'_, T, N, L> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.idx == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        Some(unsafe { (self.vec.buffer.as_ptr() as *const T).add(self.end).read() })
+    }
+}
+
+impl<T, L: LenType, const N: usize> ExactSizeIterator for Drain<'_, T, N, L> {}
+
+impl<T, L: LenType, const N: usize> iter::FusedIterator for Drain<'_, T, N, L> {}
+
+impl<T, L: LenType, const N: usize> fmt::Debug for Drain<'_, T, N, L>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T, L: LenType, const N: usize> Drop for Drain<'_, T, N, L> {
+    fn drop(&mut self) {
+        /// Moves the tail back in place even if dropping a remaining element panics.
+        struct DropGuard<'r, 'a, T, const N: usize, L: LenType>(&'r mut Drain<'a, T, N, L>);
+
+        impl<T, L: LenType, const N: usize> Drop for DropGuard<'_, '_, T, N, L> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                let start = drain.vec.len.into_usize();
+                if drain.tail_len > 0 && drain.tail_start != start {
+                    unsafe {
+                        let base = drain.vec.buffer.as_mut_ptr() as *mut T;
+                        ptr::copy(base.add(drain.tail_start), base.add(start), drain.tail_len);
+                    }
+                }
+                drain.vec.len = L::from_usize(start + drain.tail_len);
+            }
+        }
+
+        let (idx, end) = (self.idx, self.end);
+        self.idx = end;
+        let guard = DropGuard(self);
+
+        unsafe {
+            let base = guard.0.vec.buffer.as_mut_ptr() as *mut T;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(base.add(idx), end - idx));
+        }
+    }
+}
+
+/// A [`Vec`](type.Vec.html) with its capacity erased from the type.
+///
+/// `VecView<T>` is to `Vec<T, N>` what `str` is to `String`: an unsized type that is only used
+/// behind a reference, obtained with [`Vec::as_view`](type.Vec.html#method.as_view) or
+/// [`Vec::as_mut_view`](type.Vec.html#method.as_mut_view). Code that takes a `&mut VecView<T>`
+/// is compiled once for all capacities, instead of once per `N`. It offers the same methods as
+/// `Vec`, except for the ones that create vectors with a capacity `N`.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{vec::VecView, Vec};
+///
+/// fn push_checksum(packet: &mut VecView<u8>) -> Result<(), u8> {
+///     let sum = packet.iter().fold(0u8, |acc, b| acc.wrapping_add(*b));
+///     packet.push(sum)
+/// }
+///
+/// let mut a: Vec<u8, 4> = Vec::from_slice(&[1, 2, 3]).unwrap();
+/// let mut b: Vec<u8, 128> = Vec::from_slice(&[4, 5]).unwrap();
+/// push_checksum(a.as_mut_view()).unwrap();
+/// push_checksum(b.as_mut_view()).unwrap();
+/// assert_eq!(a, [1, 2, 3, 6]);
+/// assert_eq!(b, [4, 5, 9]);
+///
+/// // `a` is full
+/// assert_eq!(push_checksum(a.as_mut_view()), Err(12));
+/// ```
+pub type VecView<T, L = usize> = VecInner<T, L, [MaybeUninit<T>]>;
+
+impl<T, L: LenType> VecView<T, L> {
+    /// Returns the maximum number of elements the vector can hold.
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+impl<T, L: LenType, const N: usize> AsRef<VecView<T, L>> for Vec<T, N, L> {
+    fn as_ref(&self) -> &VecView<T, L> {
+        self.as_view()
+    }
+}
+
+impl<T, L: LenType, const N: usize> AsMut<VecView<T, L>> for Vec<T, N, L> {
+    fn as_mut(&mut self) -> &mut VecView<T, L> {
+        self.as_mut_view()
+    }
+}
+
+/// A fixed capacity vector whose backing array is aligned to at least the alignment of `A`.
+///
+/// The backing array starts at an address that is a multiple of the alignment of `A`, so buffers
+/// handed to DMA engines or flash controllers meet their alignment requirements without padding
+/// tricks. `A` is only used for its alignment and is never stored: use one of
+/// [`Align4`](struct.Align4.html), [`Align8`](struct.Align8.html),
+/// [`Align16`](struct.Align16.html) or [`Align32`](struct.Align32.html), or any other type with
+/// the required alignment.
+///
+/// An `AlignedVec` offers the same methods as [`VecView`](type.VecView.html) and stores its
+/// length as an `L` like a `Vec`. [`as_ptr`](struct.VecInner.html#method.as_ptr) returns the
+/// aligned address of the backing array.
+///
+/// # Examples
+///
+/// ```
+/// use heapless::vec::{Align32, AlignedVec};
+///
+/// static mut RX: AlignedVec<u8, 64, Align32> = AlignedVec::new();
+///
+/// let mut tx: AlignedVec<u8, 16, Align32> = AlignedVec::new();
+/// tx.extend_from_slice(&[0xAA, 0x55]).unwrap();
+/// assert_eq!(tx.as_ptr() as usize % 32, 0);
+/// assert_eq!(tx, [0xAA, 0x55]);
+/// ```
+pub type AlignedVec<T, const N: usize, A, L = usize> =
+    VecInner<T, L, Aligned<A, MaybeUninit<[T; N]>>>;
+
+impl<T, L: LenType, const N: usize, A> AlignedVec<T, N, A, L> {
+    /// Constructs a new, empty vector with a fixed capacity of `N`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity `N` doesn't fit in the length type `L`.
+    pub const fn new() -> Self {
+        assert!(N <= L::MAX, "the capacity doesn't fit in the length type");
+
+        Self {
+            phantom: PhantomData,
+            len: L::ZERO,
+            buffer: Aligned {
+                _align: [],
+                storage: MaybeUninit::uninit(),
+            },
+        }
+    }
+
+    /// Returns the maximum number of elements the vector can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T, L: LenType, const N: usize, A> Default for AlignedVec<T, N, A, L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The storage `S` of a vector, aligned to at least the alignment of `A`; see
+/// [`AlignedVec`](type.AlignedVec.html)
+#[repr(C)]
+pub struct Aligned<A, S: ?Sized> {
+    _align: [A; 0],
+    storage: S,
+}
+
+impl<T, A, S: VecStorage<T> + ?Sized> VecStorage<T> for Aligned<A, S> {
+    fn as_uninit_slice(&self) -> &[MaybeUninit<T>] {
+        self.storage.as_uninit_slice()
+    }
+
+    fn as_uninit_mut_slice(&mut self) -> &mut [MaybeUninit<T>] {
+        self.storage.as_uninit_mut_slice()
+    }
+}
+
+/// A zero-sized type aligned to 4 bytes, for use with [`AlignedVec`](type.AlignedVec.html)
+#[repr(align(4))]
+#[derive(Clone, Copy, Debug)]
+pub struct Align4;
+
+/// A zero-sized type aligned to 8 bytes, for use with [`AlignedVec`](type.AlignedVec.html)
+#[repr(align(8))]
+#[derive(Clone, Copy, Debug)]
+pub struct Align8;
+
+/// A zero-sized type aligned to 16 bytes, for use with [`AlignedVec`](type.AlignedVec.html)
+#[repr(align(16))]
+#[derive(Clone, Copy, Debug)]
+pub struct Align16;
+
+/// A zero-sized type aligned to 32 bytes, for use with [`AlignedVec`](type.AlignedVec.html)
+#[repr(align(32))]
+#[derive(Clone, Copy, Debug)]
+pub struct Align32;
+
+/// A vector backed by borrowed storage instead of an inline array.
+///
+/// The capacity of a `SliceVec` is the length of the `&mut [MaybeUninit<T>]` it is created from,
+/// so it is chosen at runtime. This allows dividing one large buffer into several vectors of
+/// different capacities, e.g. with `split_at_mut`. The elements are dropped when the `SliceVec`
+/// is dropped.
+///
+/// A `SliceVec` offers the same methods as [`VecView`](type.VecView.html), and stores its length
+/// as an `L` like a `Vec`.
+///
+/// # Examples
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use heapless::vec::SliceVec;
+///
+/// let mut storage = [MaybeUninit::<u8>::uninit(); 16];
+/// let (a, b) = storage.split_at_mut(4);
+///
+/// let mut header: SliceVec<'_, u8> = SliceVec::new(a);
+/// let mut payload: SliceVec<'_, u8> = SliceVec::new(b);
+/// assert_eq!(header.capacity(), 4);
+/// assert_eq!(payload.capacity(), 12);
+///
+/// header.extend_from_slice(&[0xAA, 0x55]).unwrap();
+/// payload.extend_from_slice(b"hello").unwrap();
+/// assert_eq!(header, [0xAA, 0x55]);
+/// assert_eq!(payload, b"hello");
+/// ```
+pub type SliceVec<'a, T, L = usize> = VecInner<T, L, &'a mut [MaybeUninit<T>]>;
+
+impl<'a, T, L: LenType> SliceVec<'a, T, L> {
+    /// Constructs a new, empty vector that stores its elements in `buffer`.
+    ///
+    /// Any values in `buffer` are ignored and overwritten.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `buffer` doesn't fit in the length type `L`.
+    pub fn new(buffer: &'a mut [MaybeUninit<T>]) -> Self {
+        assert!(
+            buffer.len() <= L::MAX,
+            "the capacity doesn't fit in the length type"
+        );
+
+        Self {
+            phantom: PhantomData,
+            len: L::ZERO,
+            buffer,
+        }
+    }
+
+    /// Returns the maximum number of elements the vector can hold.
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+impl<A, B, L1, L2, S1, S2> PartialEq<VecInner<B, L2, S2>> for VecInner<A, L1, S1>
+where
+    A: PartialEq<B>,
+    L1: LenType,
+    L2: LenType,
+    S1: VecStorage<A> + ?Sized,
+    S2: VecStorage<B> + ?Sized,
+{
+    fn eq(&self, other: &VecInner<B, L2, S2>) -> bool {
+        <[A]>::eq(self, &**other)
+    }
+}
+
+// Vec<A, N> == [B]
+impl<A, B, L: LenType, S: VecStorage<A> + ?Sized> PartialEq<[B]> for VecInner<A, L, S>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &[B]) -> bool {
+        <[A]>::eq(self, &other[..])
+    }
+}
+
+// Vec<A, N> == &[B]
+impl<A, B, L: LenType, S: VecStorage<A> + ?Sized> PartialEq<&[B]> for VecInner<A, L, S>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &&[B]) -> bool {
+        <[A]>::eq(self, &other[..])
+    }
+}
+
+// Vec<A, N> == &mut [B]
+impl<A, B, L: LenType, S: VecStorage<A> + ?Sized> PartialEq<&mut [B]> for VecInner<A, L, S>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &&mut [B]) -> bool {
+        <[A]>::eq(self, &other[..])
+    }
+}
+
+// Vec<A, N> == [B; M]
+// Equality does not require equal capacity
+impl<A, B, L: LenType, S: VecStorage<A> + ?Sized, const M: usize> PartialEq<[B; M]>
+    for VecInner<A, L, S>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &[B; M]) -> bool {
+        <[A]>::eq(self, &other[..])
+    }
+}
+
+// Vec<A, N> == &[B; M]
+// Equality does not require equal capacity
+impl<A, B, L: LenType, S: VecStorage<A> + ?Sized, const M: usize> PartialEq<&[B; M]>
+    for VecInner<A, L, S>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &&[B; M]) -> bool {
+        <[A]>::eq(self, &other[..])
+    }
+}
+
+// Implements Eq if underlying data is Eq
+impl<T, L: LenType, S: VecStorage<T> + ?Sized> Eq for VecInner<T, L, S> where T: Eq {}
+
+impl<T, L: LenType, S: VecStorage<T> + ?Sized> ops::Deref for VecInner<T, L, S> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, L: LenType, S: VecStorage<T> + ?Sized> ops::DerefMut for VecInner<T, L, S> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, L: LenType, const N: usize> AsRef<Vec<T, N, L>> for Vec<T, N, L> {
+    #[inline]
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl<T, L: LenType, const N: usize> AsMut<Vec<T, N, L>> for Vec<T, N, L> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut Self {
+        self
+    }
+}
+
+impl<T, L: LenType, S: VecStorage<T> + ?Sized> AsRef<[T]> for VecInner<T, L, S> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, L: LenType, S: VecStorage<T> + ?Sized> AsMut<[T]> for VecInner<T, L, S> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T, L: LenType, const N: usize> Clone for Vec<T, N, L>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        self.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Align32, Align8, AlignedVec, CapacityError, SliceVec, VecView};
+    use crate::Vec;
+    use core::{convert::TryFrom, fmt::Write, mem::MaybeUninit};
+
+    #[test]
+    fn static_new() {
+        static mut _V: Vec<i32, 4> = Vec::new();
+    }
+
+    #[test]
+    fn stack_new() {
+        static mut _V: Vec<i32, 4> = Vec::new();
+    }
+
+    macro_rules! droppable {
+        () => {
+            struct Droppable;
             impl Droppable {
                 fn new() -> Self {
                     unsafe {
@@ -903,223 +2414,904 @@ mod tests {
 
         let mut items = v.iter_mut();
 
-        assert_eq!(items.next(), Some(&mut 0));
-        assert_eq!(items.next(), Some(&mut 1));
-        assert_eq!(items.next(), Some(&mut 2));
-        assert_eq!(items.next(), Some(&mut 3));
-        assert_eq!(items.next(), None);
+        assert_eq!(items.next(), Some(&mut 0));
+        assert_eq!(items.next(), Some(&mut 1));
+        assert_eq!(items.next(), Some(&mut 2));
+        assert_eq!(items.next(), Some(&mut 3));
+        assert_eq!(items.next(), None);
+    }
+
+    #[test]
+    fn collect_from_iter() {
+        let slice = &[1, 2, 3];
+        let vec: Vec<i32, 4> = slice.iter().cloned().collect();
+        // PER: Auto deref did not work
+        assert_eq!(vec.as_slice(), slice);
+    }
+
+    #[test]
+    #[should_panic]
+    fn collect_from_iter_overfull() {
+        let slice = &[1, 2, 3];
+        let _vec = slice.iter().cloned().collect::<Vec<_, 2>>();
+    }
+
+    #[test]
+    fn iter_move() {
+        let mut v: Vec<i32, 4> = Vec::new();
+        v.push(0).unwrap();
+        v.push(1).unwrap();
+        v.push(2).unwrap();
+        v.push(3).unwrap();
+
+        let mut items = v.into_iter();
+
+        assert_eq!(items.next(), Some(0));
+        assert_eq!(items.next(), Some(1));
+        assert_eq!(items.next(), Some(2));
+        assert_eq!(items.next(), Some(3));
+        assert_eq!(items.next(), None);
+    }
+
+    #[test]
+    fn iter_move_drop() {
+        droppable!();
+
+        {
+            let mut vec: Vec<Droppable, 2> = Vec::new();
+            vec.push(Droppable::new()).ok().unwrap();
+            vec.push(Droppable::new()).ok().unwrap();
+            let mut items = vec.into_iter();
+            // Move all
+            let _ = items.next();
+            let _ = items.next();
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
+
+        {
+            let mut vec: Vec<Droppable, 2> = Vec::new();
+            vec.push(Droppable::new()).ok().unwrap();
+            vec.push(Droppable::new()).ok().unwrap();
+            let _items = vec.into_iter();
+            // Move none
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
+
+        {
+            let mut vec: Vec<Droppable, 2> = Vec::new();
+            vec.push(Droppable::new()).ok().unwrap();
+            vec.push(Droppable::new()).ok().unwrap();
+            let mut items = vec.into_iter();
+            let _ = items.next(); // Move partly
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
+    }
+
+    #[test]
+    fn push_and_pop() {
+        let mut v: Vec<i32, 4> = Vec::new();
+        assert_eq!(v.len(), 0);
+
+        assert_eq!(v.pop(), None);
+        assert_eq!(v.len(), 0);
+
+        v.push(0).unwrap();
+        assert_eq!(v.len(), 1);
+
+        assert_eq!(v.pop(), Some(0));
+        assert_eq!(v.len(), 0);
+
+        assert_eq!(v.pop(), None);
+        assert_eq!(v.len(), 0);
+    }
+
+    #[test]
+    fn resize_size_limit() {
+        let mut v: Vec<u8, 4> = Vec::new();
+
+        v.resize(0, 0).unwrap();
+        v.resize(4, 0).unwrap();
+        v.resize(5, 0).err().expect("full");
+    }
+
+    #[test]
+    fn resize_length_cases() {
+        let mut v: Vec<u8, 4> = Vec::new();
+
+        assert_eq!(v.len(), 0);
+
+        // Grow by 1
+        v.resize(1, 0).unwrap();
+        assert_eq!(v.len(), 1);
+
+        // Grow by 2
+        v.resize(3, 0).unwrap();
+        assert_eq!(v.len(), 3);
+
+        // Resize to current size
+        v.resize(3, 0).unwrap();
+        assert_eq!(v.len(), 3);
+
+        // Shrink by 1
+        v.resize(2, 0).unwrap();
+        assert_eq!(v.len(), 2);
+
+        // Shrink by 2
+        v.resize(0, 0).unwrap();
+        assert_eq!(v.len(), 0);
+    }
+
+    #[test]
+    fn resize_contents() {
+        let mut v: Vec<u8, 4> = Vec::new();
+
+        // New entries take supplied value when growing
+        v.resize(1, 17).unwrap();
+        assert_eq!(v[0], 17);
+
+        // Old values aren't changed when growing
+        v.resize(2, 18).unwrap();
+        assert_eq!(v[0], 17);
+        assert_eq!(v[1], 18);
+
+        // Old values aren't changed when length unchanged
+        v.resize(2, 0).unwrap();
+        assert_eq!(v[0], 17);
+        assert_eq!(v[1], 18);
+
+        // Old values aren't changed when shrinking
+        v.resize(1, 0).unwrap();
+        assert_eq!(v[0], 17);
+    }
+
+    #[test]
+    fn resize_default() {
+        let mut v: Vec<u8, 4> = Vec::new();
+
+        // resize_default is implemented using resize, so just check the
+        // correct value is being written.
+        v.resize_default(1).unwrap();
+        assert_eq!(v[0], 0);
+    }
+
+    #[test]
+    fn write() {
+        let mut v: Vec<u8, 4> = Vec::new();
+        write!(v, "{:x}", 1234).unwrap();
+        assert_eq!(&v[..], b"4d2");
+
+        assert!(write!(v, "{}", 12).is_err());
+        assert_eq!(&v[..], b"4d2");
+        write!(v, "{}", 1).unwrap();
+        assert_eq!(&v[..], b"4d21");
+        assert!(v.write_str("").is_ok());
+        assert!(v.write_char('x').is_err());
+    }
+
+    #[test]
+    fn extend_from_slice() {
+        let mut v: Vec<u8, 4> = Vec::new();
+        assert_eq!(v.len(), 0);
+        v.extend_from_slice(&[1, 2]).unwrap();
+        assert_eq!(v.len(), 2);
+        assert_eq!(v.as_slice(), &[1, 2]);
+        v.extend_from_slice(&[3]).unwrap();
+        assert_eq!(v.len(), 3);
+        assert_eq!(v.as_slice(), &[1, 2, 3]);
+        assert!(v.extend_from_slice(&[4, 5]).is_err());
+        assert_eq!(v.len(), 3);
+        assert_eq!(v.as_slice(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn from_slice() {
+        // Successful construction
+        let v: Vec<u8, 4> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(v.len(), 3);
+        assert_eq!(v.as_slice(), &[1, 2, 3]);
+
+        // Slice too large
+        assert!(Vec::<u8, 2>::from_slice(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn starts_with() {
+        let v: Vec<_, 8> = Vec::from_slice(b"ab").unwrap();
+        assert!(v.starts_with(&[]));
+        assert!(v.starts_with(b""));
+        assert!(v.starts_with(b"a"));
+        assert!(v.starts_with(b"ab"));
+        assert!(!v.starts_with(b"abc"));
+        assert!(!v.starts_with(b"ba"));
+        assert!(!v.starts_with(b"b"));
+    }
+
+    #[test]
+    fn ends_with() {
+        let v: Vec<_, 8> = Vec::from_slice(b"ab").unwrap();
+        assert!(v.ends_with(&[]));
+        assert!(v.ends_with(b""));
+        assert!(v.ends_with(b"b"));
+        assert!(v.ends_with(b"ab"));
+        assert!(!v.ends_with(b"abc"));
+        assert!(!v.ends_with(b"ba"));
+        assert!(!v.ends_with(b"a"));
+    }
+
+    #[test]
+    fn retain() {
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3, 4, 5, 6]).unwrap();
+        v.retain(|&x| x % 3 != 0);
+        assert_eq!(v, [1, 2, 4, 5]);
+
+        v.retain(|_| true);
+        assert_eq!(v, [1, 2, 4, 5]);
+
+        v.retain_mut(|x| {
+            *x *= 10;
+            *x > 10
+        });
+        assert_eq!(v, [20, 40, 50]);
+
+        v.retain(|_| false);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn retain_drop() {
+        droppable!();
+
+        {
+            let mut v: Vec<Droppable, 4> = Vec::new();
+            for _ in 0..4 {
+                v.push(Droppable::new()).ok().unwrap();
+            }
+
+            let mut i = 0;
+            v.retain(|_| {
+                i += 1;
+                i % 2 == 0
+            });
+            assert_eq!(v.len(), 2);
+            assert_eq!(unsafe { COUNT }, 2);
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
+    }
+
+    #[test]
+    fn drain() {
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3, 4, 5, 6]).unwrap();
+        let d: Vec<u8, 8> = v.drain(1..3).collect();
+        assert_eq!(d, [2, 3]);
+        assert_eq!(v, [1, 4, 5, 6]);
+
+        let mut drain = v.drain(1..=2);
+        assert_eq!(drain.len(), 2);
+        assert_eq!(drain.next_back(), Some(5));
+        core::mem::drop(drain);
+        assert_eq!(v, [1, 6]);
+
+        v.drain(2..);
+        assert_eq!(v, [1, 6]);
+
+        let d: Vec<u8, 8> = v.drain(..).rev().collect();
+        assert_eq!(d, [6, 1]);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn drain_out_of_bounds() {
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        v.drain(1..4);
+    }
+
+    #[test]
+    fn drain_drop() {
+        droppable!();
+
+        {
+            let mut v: Vec<Droppable, 4> = Vec::new();
+            for _ in 0..4 {
+                v.push(Droppable::new()).ok().unwrap();
+            }
+
+            let mut drain = v.drain(1..3);
+            core::mem::drop(drain.next());
+            assert_eq!(unsafe { COUNT }, 3);
+            core::mem::drop(drain);
+            assert_eq!(unsafe { COUNT }, 2);
+            assert_eq!(v.len(), 2);
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut v: Vec<u8, 4> = Vec::new();
+        v.insert(0, 2).unwrap();
+        v.insert(0, 1).unwrap();
+        v.insert(2, 4).unwrap();
+        v.insert(2, 3).unwrap();
+        assert_eq!(v, [1, 2, 3, 4]);
+        assert_eq!(v.insert(1, 5), Err(5));
+
+        assert_eq!(v.remove(3), 4);
+        assert_eq!(v.remove(0), 1);
+        assert_eq!(v, [2, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds() {
+        let mut v: Vec<u8, 4> = Vec::new();
+        let _ = v.insert(1, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_out_of_bounds() {
+        let mut v: Vec<u8, 4> = Vec::from_slice(&[1]).unwrap();
+        v.remove(1);
+    }
+
+    #[test]
+    fn dedup() {
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 1, 2, 3, 3, 3, 1, 4]).unwrap();
+        v.dedup();
+        assert_eq!(v, [1, 2, 3, 1, 4]);
+
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 3, 5, 2, 4, 7]).unwrap();
+        v.dedup_by_key(|x| *x % 2);
+        assert_eq!(v, [1, 2, 7]);
+
+        let mut v: Vec<u8, 8> = Vec::from_slice(&[1, 2, 4, 9, 10]).unwrap();
+        v.dedup_by(|a, b| *a - *b == 1);
+        assert_eq!(v, [1, 4, 9]);
+
+        let mut v: Vec<u8, 8> = Vec::new();
+        v.dedup();
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn dedup_drop() {
+        droppable!();
+
+        {
+            let mut v: Vec<Droppable, 4> = Vec::new();
+            for _ in 0..4 {
+                v.push(Droppable::new()).ok().unwrap();
+            }
+
+            v.dedup_by(|_, _| true);
+            assert_eq!(v.len(), 1);
+            assert_eq!(unsafe { COUNT }, 1);
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
+    }
+
+    #[test]
+    fn split_off() {
+        let mut v: Vec<u8, 4> = Vec::from_slice(&[1, 2, 3, 4]).unwrap();
+        assert_eq!(v.split_off::<2>(1), Err(CapacityError));
+        assert_eq!(v, [1, 2, 3, 4]);
+
+        let tail: Vec<u8, 2> = v.split_off(2).unwrap();
+        assert_eq!(tail, [3, 4]);
+        assert_eq!(v, [1, 2]);
+
+        let tail: Vec<u8, 0> = v.split_off(2).unwrap();
+        assert!(tail.is_empty());
+        assert_eq!(v, [1, 2]);
+
+        let tail: Vec<u8, 8> = v.split_off(0).unwrap();
+        assert_eq!(tail, [1, 2]);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_off_out_of_bounds() {
+        let mut v: Vec<u8, 4> = Vec::from_slice(&[1, 2]).unwrap();
+        let _ = v.split_off::<4>(3);
+    }
+
+    #[test]
+    fn resize_with() {
+        let mut v: Vec<u8, 4> = Vec::new();
+        v.resize_with(3, || 7).unwrap();
+        assert_eq!(v, [7, 7, 7]);
+        assert_eq!(v.resize_with(5, || 0), Err(CapacityError));
+        assert_eq!(v, [7, 7, 7]);
+        v.resize_with(1, || unreachable!()).unwrap();
+        assert_eq!(v, [7]);
+    }
+
+    #[test]
+    fn resize_with_drop() {
+        droppable!();
+
+        {
+            let mut v: Vec<Droppable, 4> = Vec::new();
+            v.resize_with(4, Droppable::new).unwrap();
+            assert_eq!(unsafe { COUNT }, 4);
+            v.resize_with(1, Droppable::new).unwrap();
+            assert_eq!(unsafe { COUNT }, 1);
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
     }
 
     #[test]
-    fn collect_from_iter() {
-        let slice = &[1, 2, 3];
-        let vec: Vec<i32, 4> = slice.iter().cloned().collect();
-        // PER: Auto deref did not work
-        assert_eq!(vec.as_slice(), slice);
+    fn spare_capacity_mut() {
+        let mut v: Vec<u8, 4> = Vec::new();
+        assert_eq!(v.spare_capacity_mut().len(), 4);
+
+        for slot in v.spare_capacity_mut() {
+            slot.write(9);
+        }
+        unsafe { v.assume_init_push_n(2) };
+        assert_eq!(v, [9, 9]);
+        assert_eq!(v.spare_capacity_mut().len(), 2);
+
+        unsafe { v.assume_init_push_n(2) };
+        assert_eq!(v, [9, 9, 9, 9]);
+        assert!(v.spare_capacity_mut().is_empty());
     }
 
     #[test]
     #[should_panic]
-    fn collect_from_iter_overfull() {
-        let slice = &[1, 2, 3];
-        let _vec = slice.iter().cloned().collect::<Vec<_, 2>>();
+    fn assume_init_push_n_overflow() {
+        let mut v: Vec<u8, 4> = Vec::new();
+        unsafe { v.assume_init_push_n(5) };
     }
 
     #[test]
-    fn iter_move() {
-        let mut v: Vec<i32, 4> = Vec::new();
-        v.push(0).unwrap();
-        v.push(1).unwrap();
-        v.push(2).unwrap();
-        v.push(3).unwrap();
+    fn into_array() {
+        let v: Vec<u8, 4> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        let v = v.into_array::<4>().unwrap_err();
+        assert_eq!(v.into_array(), Ok([1, 2, 3]));
 
-        let mut items = v.into_iter();
+        let v: Vec<u8, 3> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(<[u8; 3]>::try_from(v), Ok([1, 2, 3]));
 
-        assert_eq!(items.next(), Some(0));
-        assert_eq!(items.next(), Some(1));
-        assert_eq!(items.next(), Some(2));
-        assert_eq!(items.next(), Some(3));
-        assert_eq!(items.next(), None);
+        let v: Vec<u8, 3> = Vec::from_slice(&[1, 2]).unwrap();
+        assert_eq!(
+            <[u8; 3]>::try_from(v),
+            Err(Vec::from_slice(&[1, 2]).unwrap())
+        );
     }
 
     #[test]
-    fn iter_move_drop() {
+    fn into_array_drop() {
         droppable!();
 
         {
-            let mut vec: Vec<Droppable, 2> = Vec::new();
-            vec.push(Droppable::new()).ok().unwrap();
-            vec.push(Droppable::new()).ok().unwrap();
-            let mut items = vec.into_iter();
-            // Move all
-            let _ = items.next();
-            let _ = items.next();
+            let mut v: Vec<Droppable, 4> = Vec::new();
+            v.push(Droppable::new()).ok().unwrap();
+            v.push(Droppable::new()).ok().unwrap();
+
+            let v = v.into_array::<3>().err().unwrap();
+            assert_eq!(unsafe { COUNT }, 2);
+            let array: [Droppable; 2] = v.into_array().ok().unwrap();
+            assert_eq!(unsafe { COUNT }, 2);
+            core::mem::drop(array);
+            assert_eq!(unsafe { COUNT }, 0);
         }
 
         assert_eq!(unsafe { COUNT }, 0);
+    }
+
+    #[test]
+    fn view() {
+        let mut v: Vec<u8, 4> = Vec::from_slice(&[1, 2]).unwrap();
+        assert_eq!(v.as_view().len(), 2);
+        assert_eq!(v.as_view().capacity(), 4);
+
+        let view = v.as_mut_view();
+        view.push(3).unwrap();
+        assert_eq!(view.extend_from_slice(&[4, 5]), Err(()));
+        view.extend_from_slice(&[4]).unwrap();
+        assert!(view.is_full());
+        assert_eq!(view.push(5), Err(5));
+        assert_eq!(view.pop(), Some(4));
+        view[0] = 9;
+        assert_eq!(view.as_slice(), [9, 2, 3]);
+        assert_eq!(v, [9, 2, 3]);
+
+        v.as_mut_view().truncate(1);
+        assert_eq!(v, [9]);
+        v.as_mut_view().clear();
+        assert!(v.is_empty());
+        assert_eq!(v.as_mut_view().pop(), None);
+
+        write!(v.as_mut_view(), "{}", 1234).unwrap();
+        assert_eq!(v, b"1234");
+        assert!(write!(v.as_mut_view(), "5").is_err());
+    }
+
+    #[test]
+    fn view_zero_capacity() {
+        let mut v: Vec<u16, 0> = Vec::new();
+        assert_eq!(v.as_mut_view().capacity(), 0);
+        assert_eq!(v.as_mut_view().push(1), Err(1));
+    }
+
+    #[test]
+    fn view_drop() {
+        droppable!();
 
         {
-            let mut vec: Vec<Droppable, 2> = Vec::new();
-            vec.push(Droppable::new()).ok().unwrap();
-            vec.push(Droppable::new()).ok().unwrap();
-            let _items = vec.into_iter();
-            // Move none
+            let mut v: Vec<Droppable, 4> = Vec::new();
+            let view: &mut VecView<Droppable> = v.as_mut();
+            view.push(Droppable::new()).ok().unwrap();
+            view.push(Droppable::new()).ok().unwrap();
+            view.push(Droppable::new()).ok().unwrap();
+            assert_eq!(unsafe { COUNT }, 3);
+            core::mem::drop(view.pop());
+            view.truncate(1);
+            assert_eq!(unsafe { COUNT }, 1);
+            assert_eq!(v.len(), 1);
         }
 
         assert_eq!(unsafe { COUNT }, 0);
+    }
 
-        {
-            let mut vec: Vec<Droppable, 2> = Vec::new();
-            vec.push(Droppable::new()).ok().unwrap();
-            vec.push(Droppable::new()).ok().unwrap();
-            let mut items = vec.into_iter();
-            let _ = items.next(); // Move partly
+    #[test]
+    fn insert_sorted() {
+        let mut v: Vec<u8, 6> = Vec::new();
+        for x in [5, 1, 4, 1, 9] {
+            v.insert_sorted(x).unwrap();
         }
+        assert_eq!(v, [1, 1, 4, 5, 9]);
+        assert_eq!(v.insert_sorted(0), Ok(0));
+        assert_eq!(v.insert_sorted(3), Err(3));
 
-        assert_eq!(unsafe { COUNT }, 0);
+        let mut v: Vec<(u8, char), 4> = Vec::new();
+        assert_eq!(v.insert_sorted_by_key((2, 'a'), |x| x.0), Ok(0));
+        assert_eq!(v.insert_sorted_by_key((1, 'b'), |x| x.0), Ok(0));
+        assert_eq!(v.insert_sorted_by_key((2, 'c'), |x| x.0), Ok(2));
+        assert_eq!(v.insert_sorted_by_key((3, 'd'), |x| x.0), Ok(3));
+        assert_eq!(v, [(1, 'b'), (2, 'a'), (2, 'c'), (3, 'd')]);
+        assert_eq!(v.insert_sorted_by_key((0, 'e'), |x| x.0), Err((0, 'e')));
     }
 
     #[test]
-    fn push_and_pop() {
-        let mut v: Vec<i32, 4> = Vec::new();
-        assert_eq!(v.len(), 0);
+    fn extend_truncating() {
+        let mut v: Vec<u8, 4> = Vec::new();
+        assert_eq!(v.extend_from_slice_truncating(&[]), 0);
+        assert_eq!(v.extend_from_slice_truncating(&[1, 2, 3]), 3);
+        assert_eq!(v.extend_from_slice_truncating(&[4, 5, 6]), 1);
+        assert_eq!(v, [1, 2, 3, 4]);
 
-        assert_eq!(v.pop(), None);
-        assert_eq!(v.len(), 0);
+        let mut v: Vec<u8, 4> = Vec::new();
+        assert_eq!(v.extend_truncating(1..3), 2);
+        let mut iter = 3..;
+        assert_eq!(v.extend_truncating(&mut iter), 2);
+        assert_eq!(iter.next(), Some(5));
+        assert_eq!(v.extend_truncating(iter), 0);
+        assert_eq!(v, [1, 2, 3, 4]);
+    }
 
-        v.push(0).unwrap();
-        assert_eq!(v.len(), 1);
+    #[test]
+    fn len_type() {
+        use core::mem::size_of;
 
-        assert_eq!(v.pop(), Some(0));
-        assert_eq!(v.len(), 0);
+        assert_eq!(size_of::<Vec<u8, 4, u8>>(), 5);
+        assert_eq!(size_of::<Vec<u8, 4, u16>>(), 6);
+        assert_eq!(size_of::<Vec<u16, 4, u8>>(), 10);
 
-        assert_eq!(v.pop(), None);
-        assert_eq!(v.len(), 0);
+        let mut v: Vec<u8, 255, u8> = Vec::new();
+        for i in 0..255 {
+            v.push(i as u8).unwrap();
+        }
+        assert!(v.is_full());
+        assert_eq!(v.push(0), Err(0));
+        assert_eq!(v.len(), 255);
+        assert_eq!(v.pop(), Some(254));
+        v.truncate(3);
+        assert_eq!(v, [0, 1, 2]);
+
+        let view: &mut VecView<u8, u8> = v.as_mut_view();
+        view.push(3).unwrap();
+        assert_eq!(view.capacity(), 255);
+        assert_eq!(v, [0, 1, 2, 3]);
+
+        let d: Vec<u8, 8, u16> = v.drain(1..).collect();
+        assert_eq!(d, [1, 2, 3]);
+        assert_eq!(v, [0]);
     }
 
     #[test]
-    fn resize_size_limit() {
-        let mut v: Vec<u8, 4> = Vec::new();
+    #[should_panic]
+    fn len_type_too_small() {
+        let _: Vec<u8, 256, u8> = Vec::new();
+    }
 
-        v.resize(0, 0).unwrap();
-        v.resize(4, 0).unwrap();
-        v.resize(5, 0).err().expect("full");
+    #[test]
+    fn len_type_drop() {
+        droppable!();
+
+        {
+            let mut v: Vec<Droppable, 4, u8> = Vec::new();
+            v.push(Droppable::new()).ok().unwrap();
+            v.push(Droppable::new()).ok().unwrap();
+            v.push(Droppable::new()).ok().unwrap();
+            v.pop().unwrap();
+            assert_eq!(unsafe { COUNT }, 2);
+
+            let mut iter = v.into_iter();
+            iter.next().unwrap();
+            assert_eq!(unsafe { COUNT }, 1);
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
     }
 
     #[test]
-    fn resize_length_cases() {
-        let mut v: Vec<u8, 4> = Vec::new();
+    fn splice() {
+        let mut v: Vec<u8, 6> = Vec::from_slice(&[1, 2, 3, 4]).unwrap();
+        v.splice(1..3, [5]).unwrap();
+        assert_eq!(v, [1, 5, 4]);
+        v.splice(3.., [6, 7, 8]).unwrap();
+        assert_eq!(v, [1, 5, 4, 6, 7, 8]);
+        assert_eq!(v.splice(0..0, [0]), Err(CapacityError));
+        v.splice(.., core::iter::empty()).unwrap();
+        assert!(v.is_empty());
+    }
 
-        assert_eq!(v.len(), 0);
+    struct ShortIter(u8);
 
-        // Grow by 1
-        v.resize(1, 0).unwrap();
-        assert_eq!(v.len(), 1);
+    impl Iterator for ShortIter {
+        type Item = u8;
 
-        // Grow by 2
-        v.resize(3, 0).unwrap();
-        assert_eq!(v.len(), 3);
+        fn next(&mut self) -> Option<u8> {
+            None
+        }
 
-        // Resize to current size
-        v.resize(3, 0).unwrap();
-        assert_eq!(v.len(), 3);
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.0 as usize, Some(self.0 as usize))
+        }
+    }
 
-        // Shrink by 1
-        v.resize(2, 0).unwrap();
-        assert_eq!(v.len(), 2);
+    impl ExactSizeIterator for ShortIter {}
 
-        // Shrink by 2
-        v.resize(0, 0).unwrap();
-        assert_eq!(v.len(), 0);
+    #[test]
+    fn splice_short_iter() {
+        let mut v: Vec<u8, 6> = Vec::from_slice(&[1, 2, 3, 4]).unwrap();
+        v.splice(1..2, ShortIter(3)).unwrap();
+        assert_eq!(v, [1, 3, 4]);
     }
 
     #[test]
-    fn resize_contents() {
-        let mut v: Vec<u8, 4> = Vec::new();
+    fn splice_drop() {
+        droppable!();
 
-        // New entries take supplied value when growing
-        v.resize(1, 17).unwrap();
-        assert_eq!(v[0], 17);
+        {
+            let mut v: Vec<Droppable, 4> = Vec::new();
+            for _ in 0..3 {
+                v.push(Droppable::new()).ok().unwrap();
+            }
 
-        // Old values aren't changed when growing
-        v.resize(2, 18).unwrap();
-        assert_eq!(v[0], 17);
-        assert_eq!(v[1], 18);
+            let new = [Droppable::new(), Droppable::new()];
+            assert_eq!(unsafe { COUNT }, 5);
+            v.splice(..2, new).unwrap();
+            assert_eq!(unsafe { COUNT }, 3);
+            assert_eq!(v.len(), 3);
+        }
 
-        // Old values aren't changed when length unchanged
-        v.resize(2, 0).unwrap();
-        assert_eq!(v[0], 17);
-        assert_eq!(v[1], 18);
+        assert_eq!(unsafe { COUNT }, 0);
 
-        // Old values aren't changed when shrinking
-        v.resize(1, 0).unwrap();
-        assert_eq!(v[0], 17);
+        struct PanicIter;
+
+        impl Iterator for PanicIter {
+            type Item = Droppable;
+
+            fn next(&mut self) -> Option<Droppable> {
+                panic!("PanicIter")
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (1, Some(1))
+            }
+        }
+
+        impl ExactSizeIterator for PanicIter {}
+
+        {
+            let mut v: Vec<Droppable, 4> = Vec::new();
+            for _ in 0..4 {
+                v.push(Droppable::new()).ok().unwrap();
+            }
+
+            // the tail of 3 elements is longer than the replacement
+            let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                v.splice(..1, PanicIter).unwrap();
+            }));
+            assert!(res.is_err());
+            assert_eq!(v.len(), 0);
+        }
+
+        // the drained element is dropped and the tail is leaked, but nothing is dropped twice
+        assert_eq!(unsafe { COUNT }, 3);
     }
 
     #[test]
-    fn resize_default() {
-        let mut v: Vec<u8, 4> = Vec::new();
+    fn static_slice() {
+        static mut V: Vec<u8, 4> = Vec::new();
 
-        // resize_default is implemented using resize, so just check the
-        // correct value is being written.
-        v.resize_default(1).unwrap();
-        assert_eq!(v[0], 0);
+        let v: &'static mut Vec<u8, 4> = unsafe { &mut *core::ptr::addr_of_mut!(V) };
+        v.push(1).unwrap();
+        v.push(2).unwrap();
+        let s: &'static mut [u8] = v.as_mut_slice();
+        s[1] = 3;
+        assert_eq!(s, [1, 3]);
     }
 
     #[test]
-    fn write() {
+    fn extend_from_copy_slice() {
         let mut v: Vec<u8, 4> = Vec::new();
-        write!(v, "{:x}", 1234).unwrap();
-        assert_eq!(&v[..], b"4d2");
+        v.extend_from_copy_slice(&[]).unwrap();
+        v.extend_from_copy_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(v.extend_from_copy_slice(&[4, 5]), Err(CapacityError));
+        assert_eq!(v, [1, 2, 3]);
+        unsafe { v.extend_from_slice_unchecked(&[4]) };
+        assert_eq!(v, [1, 2, 3, 4]);
+        v.extend_from_copy_slice(&[]).unwrap();
     }
 
     #[test]
-    fn extend_from_slice() {
-        let mut v: Vec<u8, 4> = Vec::new();
-        assert_eq!(v.len(), 0);
-        v.extend_from_slice(&[1, 2]).unwrap();
-        assert_eq!(v.len(), 2);
-        assert_eq!(v.as_slice(), &[1, 2]);
-        v.extend_from_slice(&[3]).unwrap();
-        assert_eq!(v.len(), 3);
-        assert_eq!(v.as_slice(), &[1, 2, 3]);
-        assert!(v.extend_from_slice(&[4, 5]).is_err());
-        assert_eq!(v.len(), 3);
-        assert_eq!(v.as_slice(), &[1, 2, 3]);
+    fn slice_vec() {
+        let mut storage = [MaybeUninit::<u8>::uninit(); 8];
+        let (a, b) = storage.split_at_mut(3);
+        let mut a: SliceVec<'_, u8> = SliceVec::new(a);
+        let mut b: SliceVec<'_, u8, u8> = SliceVec::new(b);
+
+        a.push(1).unwrap();
+        a.extend_from_slice(&[2, 3]).unwrap();
+        assert!(a.is_full());
+        assert_eq!(a.push(4), Err(4));
+        assert_eq!(a.extend_from_slice(&[4]), Err(()));
+        assert_eq!(a, [1, 2, 3]);
+        assert_eq!(a.pop(), Some(3));
+        a.clear();
+        assert_eq!(a.pop(), None);
+
+        write!(b, "{}", 12345).unwrap();
+        assert_eq!(b, b"12345");
+        assert!(write!(b, "6").is_err());
+        b[0] = b'0';
+        assert_eq!(&*b, b"02345");
+        b.truncate(2);
+        assert_eq!(b.as_slice(), b"02");
+
+        // the whole `Vec` API is available
+        b.insert(0, b'1').unwrap();
+        b.retain(|&x| x != b'0');
+        assert_eq!(b, b"12");
+        assert_eq!(b.remaining_capacity(), 3);
     }
 
     #[test]
-    fn from_slice() {
-        // Successful construction
-        let v: Vec<u8, 4> = Vec::from_slice(&[1, 2, 3]).unwrap();
-        assert_eq!(v.len(), 3);
-        assert_eq!(v.as_slice(), &[1, 2, 3]);
+    fn slice_vec_zero_capacity() {
+        let mut v: SliceVec<'_, i32> = SliceVec::new(&mut []);
+        assert_eq!(v.capacity(), 0);
+        assert_eq!(v.push(1), Err(1));
+    }
 
-        // Slice too large
-        assert!(Vec::<u8, 2>::from_slice(&[1, 2, 3]).is_err());
+    #[test]
+    fn slice_vec_drop() {
+        droppable!();
+
+        {
+            let mut storage = [
+                MaybeUninit::uninit(),
+                MaybeUninit::uninit(),
+                MaybeUninit::uninit(),
+            ];
+            let mut v: SliceVec<'_, _> = SliceVec::new(&mut storage);
+            v.push(Droppable::new()).ok().unwrap();
+            v.push(Droppable::new()).ok().unwrap();
+            v.push(Droppable::new()).ok().unwrap();
+            assert_eq!(unsafe { COUNT }, 3);
+            core::mem::drop(v.pop());
+            v.truncate(1);
+            assert_eq!(unsafe { COUNT }, 1);
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
     }
 
     #[test]
-    fn starts_with() {
-        let v: Vec<_, 8> = Vec::from_slice(b"ab").unwrap();
-        assert!(v.starts_with(&[]));
-        assert!(v.starts_with(b""));
-        assert!(v.starts_with(b"a"));
-        assert!(v.starts_with(b"ab"));
-        assert!(!v.starts_with(b"abc"));
-        assert!(!v.starts_with(b"ba"));
-        assert!(!v.starts_with(b"b"));
+    fn try_from_iter() {
+        let v: Vec<u8, 3> = Vec::try_from_iter([1, 2, 3]).unwrap();
+        assert_eq!(v, [1, 2, 3]);
+        let v: Vec<u8, 3> = Vec::try_from_iter(core::iter::empty()).unwrap();
+        assert!(v.is_empty());
+        assert_eq!(Vec::<u8, 3>::try_from_iter(0..), Err(CapacityError));
     }
 
     #[test]
-    fn ends_with() {
-        let v: Vec<_, 8> = Vec::from_slice(b"ab").unwrap();
-        assert!(v.ends_with(&[]));
-        assert!(v.ends_with(b""));
-        assert!(v.ends_with(b"b"));
-        assert!(v.ends_with(b"ab"));
-        assert!(!v.ends_with(b"abc"));
-        assert!(!v.ends_with(b"ba"));
-        assert!(!v.ends_with(b"a"));
+    fn aligned_vec() {
+        use core::mem::align_of;
+
+        assert_eq!(align_of::<AlignedVec<u8, 3, Align32>>(), 32);
+
+        let mut storage = [AlignedVec::<u8, 3, Align8>::new(), AlignedVec::new()];
+        for v in &mut storage {
+            assert_eq!(v.as_ptr() as usize % 8, 0);
+            v.push(1).unwrap();
+            v.extend_from_slice(&[2, 3]).unwrap();
+            assert!(v.is_full());
+            assert_eq!(v.push(4), Err(4));
+            assert_eq!(v.extend_from_slice(&[4]), Err(()));
+            assert_eq!(*v, [1, 2, 3]);
+        }
+
+        let v = &mut storage[1];
+        assert_eq!(v.pop(), Some(3));
+        v.truncate(1);
+        assert_eq!(v.spare_capacity_mut().len(), 2);
+        unsafe {
+            v.as_mut_ptr().add(1).write(7);
+            v.set_len(2);
+        }
+        assert_eq!(*v, [1, 7]);
+        v.clear();
+        assert!(v.is_empty());
+
+        // the whole `Vec` API and the length type are available
+        let mut v: AlignedVec<u8, 200, Align32, u8> = AlignedVec::new();
+        assert_eq!(v.as_ptr() as usize % 32, 0);
+        v.extend_from_copy_slice(&[3, 1, 2]).unwrap();
+        assert_eq!(v.insert_sorted(0), Ok(0));
+        assert_eq!(v, [0, 3, 1, 2]);
+        assert_eq!(v.remaining_capacity(), 196);
+    }
+
+    #[test]
+    fn aligned_vec_drop() {
+        droppable!();
+
+        {
+            let mut v: AlignedVec<Droppable, 3, Align32> = AlignedVec::new();
+            v.push(Droppable::new()).ok().unwrap();
+            v.push(Droppable::new()).ok().unwrap();
+            v.push(Droppable::new()).ok().unwrap();
+            core::mem::drop(v.pop());
+            v.truncate(1);
+            assert_eq!(unsafe { COUNT }, 1);
+            v.push(Droppable::new()).ok().unwrap();
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
+    }
+
+    #[test]
+    fn resize_capacity() {
+        droppable!();
+
+        {
+            let mut v: Vec<Droppable, 2> = Vec::new();
+            v.push(Droppable::new()).ok().unwrap();
+            v.push(Droppable::new()).ok().unwrap();
+
+            let mut v: Vec<Droppable, 4> = v.resize_capacity().ok().unwrap();
+            assert_eq!(unsafe { COUNT }, 2);
+            v.push(Droppable::new()).ok().unwrap();
+
+            let v = v.resize_capacity::<2>().err().unwrap();
+            assert_eq!(v.len(), 3);
+            assert_eq!(unsafe { COUNT }, 3);
+        }
+
+        assert_eq!(unsafe { COUNT }, 0);
     }
 }
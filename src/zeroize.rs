@@ -0,0 +1,134 @@
+//! Wiping containers that hold secrets
+//!
+//! With the `zeroize` feature, `Vec`, `String` and `LinearMap` implement [`Zeroize`], which
+//! wipes their whole backing storage, including the spare capacity beyond the length. The
+//! containers themselves don't wipe their storage when dropped; use [`ZeroizingVec`] and
+//! [`ZeroizingString`] for that.
+//!
+//! [`Zeroize`]: https://docs.rs/zeroize/latest/zeroize/trait.Zeroize.html
+//! [`ZeroizingVec`]: type.ZeroizingVec.html
+//! [`ZeroizingString`]: type.ZeroizingString.html
+
+use crate::{sealed::vec::LenType, LinearMap, String, Vec};
+use ::zeroize::{Zeroize, Zeroizing};
+
+/// A [`Vec`](../type.Vec.html) whose whole backing array is zeroized when it is dropped
+///
+/// # Examples
+///
+/// ```
+/// use heapless::{zeroize::ZeroizingVec, Vec};
+///
+/// let mut key = ZeroizingVec::new(Vec::<u8, 32>::new());
+/// key.extend_from_slice(&[0x42; 32]).unwrap();
+/// // the key is wiped here
+/// drop(key);
+/// ```
+pub type ZeroizingVec<T, const N: usize, L = usize> = Zeroizing<Vec<T, N, L>>;
+
+/// A [`String`](../struct.String.html) whose whole backing array is zeroized when it is dropped
+pub type ZeroizingString<const N: usize, L = usize> = Zeroizing<String<N, L>>;
+
+/// Zeroizes the elements and the whole backing array, including the spare capacity beyond the
+/// length, then clears the vector.
+impl<T, L: LenType, const N: usize> Zeroize for Vec<T, N, L>
+where
+    T: Zeroize,
+{
+    fn zeroize(&mut self) {
+        // zero the spare capacity first, while `len` still marks the initialized elements
+        self.spare_capacity_mut().zeroize();
+        self.iter_mut().zeroize();
+        self.clear();
+    }
+}
+
+/// Zeroizes the whole backing array and clears the string.
+impl<L: LenType, const N: usize> Zeroize for String<N, L> {
+    fn zeroize(&mut self) {
+        // NOTE(unsafe) the string is left empty, which is valid UTF-8
+        unsafe { self.as_mut_vec() }.zeroize();
+    }
+}
+
+/// Zeroizes the keys, values and the whole backing array, then clears the map.
+impl<K, V, const N: usize> Zeroize for LinearMap<K, V, N>
+where
+    K: Zeroize,
+    V: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.buffer.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{mem::ManuallyDrop, ptr};
+
+    fn assert_zeroed<const N: usize>(v: &mut Vec<u8, N>) {
+        assert!(v.is_empty());
+        for b in v.spare_capacity_mut() {
+            assert_eq!(unsafe { b.assume_init() }, 0);
+        }
+    }
+
+    #[test]
+    fn vec() {
+        let mut v: Vec<u8, 4> = Vec::from_slice(&[1, 2, 3, 4]).unwrap();
+        // leave stale bytes beyond the length
+        v.truncate(2);
+        v.zeroize();
+        assert_zeroed(&mut v);
+    }
+
+    #[test]
+    fn string() {
+        let mut s: String<8> = String::from("secret");
+        s.truncate(1);
+        s.zeroize();
+        assert!(s.is_empty());
+        assert_zeroed(unsafe { s.as_mut_vec() });
+    }
+
+    #[test]
+    fn linear_map() {
+        let mut map: LinearMap<u8, u32, 4> = LinearMap::new();
+        map.insert(1, 0xdead_beef).unwrap();
+        map.insert(2, 0xcafe_babe).unwrap();
+        map.zeroize();
+        assert!(map.is_empty());
+        for slot in map.buffer.spare_capacity_mut() {
+            assert_eq!(unsafe { slot.as_ptr().read() }, (0, 0));
+        }
+    }
+
+    #[test]
+    fn zeroizing() {
+        let mut key = ZeroizingVec::new(Vec::<u8, 16>::new());
+        key.extend_from_slice(&[0xAA; 16]).unwrap();
+        assert_eq!(key.len(), 16);
+        key.zeroize();
+        assert!(key.is_empty());
+    }
+
+    #[test]
+    fn zeroize_on_drop() {
+        let mut key = ManuallyDrop::new(ZeroizingVec::new(Vec::<u8, 16>::new()));
+        key.extend_from_slice(&[0xAA; 16]).unwrap();
+        key.truncate(4);
+
+        // NOTE(unsafe) the storage outlives the drop, so the wipe can be observed; dropping a
+        // `Vec<u8, _>` leaves it in a valid, empty state
+        unsafe { ptr::drop_in_place(&mut *key) };
+        assert_zeroed(&mut key);
+
+        let mut password = ManuallyDrop::new(ZeroizingString::new(String::<8>::from("hunter2")));
+        unsafe {
+            ptr::drop_in_place(&mut *password);
+            assert!(password.is_empty());
+            assert_zeroed(password.as_mut_vec());
+        }
+    }
+}